@@ -1,18 +1,74 @@
 use core::fmt;
+use serde::Serialize;
+
+use crate::signers::ThresholdLevel;
+
+/// Signers required at each threshold level of a multisig account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MultisigLevels {
+    /// Threshold level the account was classified against
+    pub level: ThresholdLevel,
+    /// Signers required for low threshold operations, `None` if unreachable
+    pub low: Option<u8>,
+    /// Signers required for medium threshold operations, `None` if unreachable
+    pub medium: Option<u8>,
+    /// Signers required for high threshold operations, `None` if unreachable
+    pub high: Option<u8>,
+    /// Number of signers with a non-zero weight
+    pub total: u8,
+}
+
+impl MultisigLevels {
+    /// Returns the signers required at the given level
+    pub fn required(&self, level: ThresholdLevel) -> Option<u8> {
+        match level {
+            ThresholdLevel::Low => self.low,
+            ThresholdLevel::Medium => self.medium,
+            ThresholdLevel::High => self.high,
+        }
+    }
+}
+
+impl fmt::Display for MultisigLevels {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let fmt_level = |level| match self.required(level) {
+            Some(n) => format!("{} {}/{}", level, n, self.total),
+            None => format!("{} unreachable", level),
+        };
+        write!(
+            f,
+            "{}/{} at {} threshold ({}, {}, {})",
+            self.required(self.level).unwrap_or_default(),
+            self.total,
+            self.level,
+            fmt_level(ThresholdLevel::Low),
+            fmt_level(ThresholdLevel::Medium),
+            fmt_level(ThresholdLevel::High)
+        )
+    }
+}
 
 /// Represents different types of Stellar accounts.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum AccountType {
     /// Smart Contract
     Contract,
-    /// N/M Multisig account
-    Multisig(u8, u8),
-    /// Account with no active signers
+    /// N/M Multisig account, with the N/M result for each threshold level
+    Multisig(MultisigLevels),
+    /// Account whose signers cannot reach the selected threshold
     Deactivated,
-    /// Hot wallet (single signer with full control)
+    /// Account which does not exist on-chain, whoever holds the key can create it
+    Unfunded,
+    /// Hash-x signer reaching the selected threshold, anyone knowing the preimage has control
+    HashX,
+    /// Hot wallet (single signer reaching the selected threshold)
     HotWallet,
     /// Multi-Party Computation wallet
     MPC,
+    /// Single signer account whose transaction history is too short, or could not be fetched,
+    /// to tell a hot wallet from MPC
+    Inconclusive,
 }
 
 impl fmt::Display for AccountType {
@@ -20,11 +76,38 @@ impl fmt::Display for AccountType {
         match self {
             AccountType::Contract => write!(f, "Contract"),
             AccountType::Deactivated => write!(f, "Deactivated Account"),
-            AccountType::Multisig(threshold, total) => {
-                write!(f, "Multisig {}/{}", threshold, total)
+            AccountType::Unfunded => {
+                write!(
+                    f,
+                    "Unfunded Account (not created yet, the key holder can create it)"
+                )
             }
+            AccountType::Multisig(levels) => write!(f, "Multisig {}", levels),
+            AccountType::HashX => write!(f, "Hash-X Signer (preimage holder has control)"),
             AccountType::HotWallet => write!(f, "Hot Wallet"),
             AccountType::MPC => write!(f, "MPC"),
+            AccountType::Inconclusive => {
+                write!(f, "Inconclusive (not enough transaction history to tell)")
+            }
+        }
+    }
+}
+
+/// How sure the tool is about a classification, from least to most sure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Confidence {
+    Low,
+    Medium,
+    High,
+}
+
+impl fmt::Display for Confidence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Confidence::Low => write!(f, "low"),
+            Confidence::Medium => write!(f, "medium"),
+            Confidence::High => write!(f, "high"),
         }
     }
 }
@@ -0,0 +1,118 @@
+use core::fmt;
+use serde::Serialize;
+use stellar_xdr::curr::ScVal;
+
+use crate::{
+    account_type::AccountType,
+    storage_helper::{AddressType, Durability, ValueAddress},
+    ttl::TtlStatus,
+};
+
+/// Reason why the admin chain walk stopped.
+#[derive(Debug, Clone, Serialize)]
+pub enum ChainEnd {
+    /// The last admin is an EOA
+    Eoa,
+    /// The last admin is a contract without an admin of its own
+    NoAdmin,
+    /// The maximum recursion depth was reached
+    MaxDepth,
+    /// The last admin was already visited earlier in the chain
+    Cycle,
+    /// The last admin is a contract which does not exist on-chain
+    MissingContract,
+    /// The admin of the last admin contract could not be looked up, with the error
+    LookupFailed(String),
+}
+
+impl ChainEnd {
+    /// Describes why the walk stopped
+    pub fn description(&self) -> String {
+        match self {
+            ChainEnd::Eoa => "ends at an EOA".to_string(),
+            ChainEnd::NoAdmin => "ends at a contract without an admin".to_string(),
+            ChainEnd::MaxDepth => "stops at the maximum depth".to_string(),
+            ChainEnd::Cycle => "loops back to a contract already visited".to_string(),
+            ChainEnd::MissingContract => "ends at a contract which does not exist".to_string(),
+            ChainEnd::LookupFailed(e) => {
+                format!("stops at a contract whose admin lookup failed, {}", e)
+            }
+        }
+    }
+}
+
+/// A single admin lookup in the chain.
+#[derive(Debug, Clone, Serialize)]
+pub struct AdminLink {
+    /// Contract whose storage holds the admin
+    pub contract: String,
+    /// Storage key the admin was found under
+    pub key: ScVal,
+    /// Storage the admin was found in
+    pub durability: Durability,
+    /// TTL of the admin entry
+    pub ttl: Option<TtlStatus>,
+    /// TTL of the contract instance
+    pub instance_ttl: Option<TtlStatus>,
+    /// Decoded admin address
+    pub admin: AddressType,
+    /// Every address found in the stored value, with its path inside the value
+    pub addresses: Vec<ValueAddress>,
+    /// Every entry matching the role's keys, only filled when there is more than one
+    pub matches: Vec<AdminMatch>,
+    /// Whether the matching entries hold different admins
+    pub conflict: bool,
+    /// Storage lookups which failed, in which case the matches may be incomplete
+    pub lookup_errors: Vec<String>,
+}
+
+/// A storage entry matching one of the role's keys.
+#[derive(Debug, Clone, Serialize)]
+pub struct AdminMatch {
+    /// Storage key of the entry
+    pub key: ScVal,
+    /// Storage the entry was found in
+    pub durability: Durability,
+    /// TTL of the entry
+    pub ttl: Option<TtlStatus>,
+    /// Decoded admin address
+    pub admin: AddressType,
+    /// Every address found in the stored value, with its path inside the value
+    pub addresses: Vec<ValueAddress>,
+    /// Classification of the admin, only filled on conflicts
+    pub account_type: Option<AccountType>,
+    /// Why the admin could not be classified, in which case it is reported as inconclusive
+    pub classification_error: Option<String>,
+}
+
+/// Admin addresses found by following the target's admin through nested admin contracts.
+#[derive(Debug, Clone, Serialize)]
+pub struct AdminChain {
+    /// Admin lookups in the order they were made, starting with the target's own admin
+    pub links: Vec<AdminLink>,
+    /// Why the walk stopped
+    pub end: ChainEnd,
+}
+
+impl AdminChain {
+    /// Returns the last admin of the chain, the one which ultimately controls the target.
+    pub fn admin(&self) -> Option<&AddressType> {
+        self.links.last().map(|link| &link.admin)
+    }
+}
+
+impl fmt::Display for AdminChain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let links: Vec<String> = self.links.iter().map(|l| l.admin.to_string()).collect();
+        write!(f, "{}", links.join(" -> "))?;
+
+        match &self.end {
+            ChainEnd::Eoa => Ok(()),
+            ChainEnd::NoAdmin => write!(f, " (no further admin)"),
+            ChainEnd::MaxDepth => write!(f, " (max depth reached)"),
+            ChainEnd::Cycle => write!(f, " (cycle)"),
+            ChainEnd::MissingContract => write!(f, " (contract does not exist)"),
+            ChainEnd::LookupFailed(_) => write!(f, " (lookup failed)"),
+        }
+    }
+}
@@ -0,0 +1,348 @@
+use serde::Serialize;
+use std::{fs, mem, path::Path, str::FromStr, sync::Arc};
+use stellar_xdr::curr::ScAddress;
+use tokio::{sync::Semaphore, task::JoinSet};
+
+use crate::config::Config;
+use admin_checker::{Error, Report};
+
+/// A single target read from the batch input file.
+#[derive(Debug, Clone)]
+pub struct BatchTarget {
+    /// Optional label given next to the address in CSV input
+    pub label: Option<String>,
+    /// Contract ID or G-address to analyze
+    pub address: String,
+}
+
+/// Outcome of the analysis of a single batch target.
+#[derive(Debug, Serialize)]
+pub struct BatchEntry {
+    pub label: Option<String>,
+    pub target: String,
+    pub report: Option<Report>,
+    pub error: Option<String>,
+}
+
+impl BatchEntry {
+    pub fn new(target: BatchTarget, result: Result<Report, Error>) -> Self {
+        let (report, error) = match result {
+            Ok(report) => (Some(report), None),
+            Err(e) => (None, Some(e.to_string())),
+        };
+        Self {
+            label: target.label,
+            target: target.address,
+            report,
+            error,
+        }
+    }
+}
+
+/// Reads the list of targets to analyze.
+///
+/// Each line holds either a single address, or comma separated fields where one of
+/// them is the address and the rest form the label. Fields may be quoted as in CSV,
+/// so a label can contain commas; quoted fields cannot span several lines. Empty
+/// lines and lines starting with `#` are skipped, as is a CSV header naming an `address`
+/// or `target` column before the first target.
+///
+/// # Arguments
+/// * `path` - Path to the input file
+pub fn read_targets(path: &Path) -> Result<Vec<BatchTarget>, Error> {
+    let content = fs::read_to_string(path).map_err(|e| Error::InputLoadFailure {
+        path: path.to_path_buf(),
+        source: Arc::new(e),
+    })?;
+
+    let mut targets = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields = csv_fields(line);
+        let address = fields.iter().position(|f| ScAddress::from_str(f).is_ok());
+
+        let target = match address {
+            Some(pos) => {
+                let label: Vec<&str> = fields
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, f)| *j != pos && !f.is_empty())
+                    .map(|(_, f)| f.as_str())
+                    .collect();
+                BatchTarget {
+                    label: (!label.is_empty()).then(|| label.join(",")),
+                    address: fields[pos].clone(),
+                }
+            }
+            None if targets.is_empty() && is_header(&fields) => continue,
+            // Keep malformed lines so they show up as failures in the report
+            None => BatchTarget {
+                label: None,
+                address: line.to_string(),
+            },
+        };
+        targets.push(target);
+    }
+
+    Ok(targets)
+}
+
+/// Analyzes all targets, running at most `concurrency` analyses at the same time.
+///
+/// Failures are recorded per target instead of aborting the whole run.
+/// Entries are returned in the same order as the targets.
+///
+/// # Arguments
+/// * `config` - The configuration shared by all targets
+/// * `targets` - The targets to analyze
+pub async fn run(config: &Config, targets: Vec<BatchTarget>) -> Vec<BatchEntry> {
+    let semaphore = Arc::new(Semaphore::new(config.concurrency().max(1)));
+    let mut tasks = JoinSet::new();
+
+    for (i, target) in targets.iter().enumerate() {
+        let runner = config.to_runner_for(&target.address);
+        let semaphore = semaphore.clone();
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            let result = match runner {
+                Ok(runner) => runner.analyze().await,
+                Err(e) => Err(e),
+            };
+            (i, result)
+        });
+    }
+
+    let mut results: Vec<Option<Result<Report, Error>>> = targets.iter().map(|_| None).collect();
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok((i, result)) = joined {
+            results[i] = Some(result);
+        }
+    }
+
+    targets
+        .into_iter()
+        .zip(results)
+        .map(|(target, result)| {
+            BatchEntry::new(target, result.unwrap_or(Err(Error::AnalysisAborted)))
+        })
+        .collect()
+}
+
+/// Renders the batch entries as an aligned text table, one row per role.
+pub fn to_table(entries: &[BatchEntry]) -> String {
+    let rows: Vec<[String; 5]> = entries
+        .iter()
+        .flat_map(rows)
+        .map(
+            |[label, target, role, admin, account_type, confidence, error]| {
+                let result = if error.is_empty() {
+                    format!("{} ({} confidence)", account_type, confidence)
+                } else {
+                    format!("error: {}", error)
+                };
+                [label, target, role, admin, result]
+            },
+        )
+        .collect();
+    let header = ["LABEL", "TARGET", "ROLE", "ADMIN", "RESULT"].map(String::from);
+
+    let mut widths = header.clone().map(|h| h.len());
+    for r in &rows {
+        for (w, field) in widths.iter_mut().zip(r) {
+            *w = (*w).max(field.len());
+        }
+    }
+
+    std::iter::once(&header)
+        .chain(&rows)
+        .map(|r| {
+            r.iter()
+                .zip(widths)
+                .map(|(field, w)| format!("{:<w$}", field, w = w))
+                .collect::<Vec<_>>()
+                .join("  ")
+                .trim_end()
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Renders the batch entries as CSV, one row per role.
+pub fn to_csv(entries: &[BatchEntry]) -> String {
+    let mut lines = vec!["label,target,role,admin,account_type,confidence,error".to_string()];
+    for row in entries.iter().flat_map(rows) {
+        lines.push(
+            row.iter()
+                .map(|f| csv_escape(f))
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+    }
+    lines.join("\n")
+}
+
+/// Builds the label, target, role, admin, account type, confidence and error fields of each role.
+fn rows(entry: &BatchEntry) -> Vec<[String; 7]> {
+    let label = entry.label.clone().unwrap_or_default();
+    let target = entry.target.clone();
+
+    let Some(report) = &entry.report else {
+        let error = entry.error.clone().unwrap_or_default();
+        return vec![[
+            label,
+            target,
+            String::new(),
+            String::new(),
+            String::new(),
+            String::new(),
+            error,
+        ]];
+    };
+
+    let resolved = report.roles.iter().map(|r| {
+        [
+            label.clone(),
+            target.clone(),
+            r.role.clone(),
+            r.admin.to_string(),
+            r.account_type.to_string(),
+            r.confidence.to_string(),
+            String::new(),
+        ]
+    });
+    let missing = report.missing_roles.iter().map(|m| {
+        [
+            label.clone(),
+            target.clone(),
+            m.role.clone(),
+            String::new(),
+            String::new(),
+            String::new(),
+            m.error.clone(),
+        ]
+    });
+    resolved.chain(missing).collect()
+}
+
+/// Quotes a CSV field if needed.
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Whether the fields name the address column, as in a CSV header
+fn is_header(fields: &[String]) -> bool {
+    fields
+        .iter()
+        .any(|f| ["address", "target"].contains(&f.to_ascii_lowercase().as_str()))
+}
+
+/// Splits a CSV line into its trimmed fields, unquoting quoted ones.
+fn csv_fields(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            '"' if quoted || field.trim().is_empty() => {
+                if !quoted {
+                    field.clear();
+                }
+                quoted = !quoted;
+            }
+            ',' if !quoted => fields.push(mem::take(&mut field).trim().to_string()),
+            c => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const CONTRACT: &str = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC";
+    const ACCOUNT: &str = "GBXGQJWVLWOYHFLVTKWV5FGHA3LNYY2JQKM7OAJAUEQFU6LPCSEFVXON";
+
+    fn read(content: &str) -> Vec<(Option<String>, String)> {
+        // Tests run in parallel, each needs a file of its own
+        static FILES: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "admin-checker-targets-{}-{}",
+            std::process::id(),
+            FILES.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&path, content).unwrap();
+        let targets = read_targets(&path);
+        fs::remove_file(&path).unwrap();
+        targets
+            .unwrap()
+            .into_iter()
+            .map(|t| (t.label, t.address))
+            .collect()
+    }
+
+    #[test]
+    fn splits_quoted_csv_fields() {
+        assert_eq!(csv_fields("a, b ,c"), ["a", "b", "c"]);
+        assert_eq!(csv_fields(r#""Acme, Inc",x"#), ["Acme, Inc", "x"]);
+        assert_eq!(csv_fields(r#" "say ""hi""" , x"#), [r#"say "hi""#, "x"]);
+        assert_eq!(csv_fields(r#"6" pipe,x"#), [r#"6" pipe"#, "x"]);
+        assert_eq!(csv_fields(""), [""]);
+    }
+
+    #[test]
+    fn reads_targets_with_labels() {
+        let content = format!(
+            "label,address\n\
+             # comment\n\
+             \n\
+             {CONTRACT}\n\
+             pool,{CONTRACT}\n\
+             \"Acme, Inc\",{ACCOUNT}\n\
+             {ACCOUNT},treasury,admin\n\
+             not an address\n"
+        );
+        assert_eq!(
+            read(&content),
+            [
+                (None, CONTRACT.to_string()),
+                (Some("pool".to_string()), CONTRACT.to_string()),
+                (Some("Acme, Inc".to_string()), ACCOUNT.to_string()),
+                (Some("treasury,admin".to_string()), ACCOUNT.to_string()),
+                (None, "not an address".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn only_skips_known_headers() {
+        let content = format!("# targets\nTarget,Label\n{CONTRACT}\n");
+        assert_eq!(read(&content), [(None, CONTRACT.to_string())]);
+
+        // A mistyped address on the first row is a failure, not a header
+        let content = format!("pool,CBADADDR\n{CONTRACT}\n");
+        assert_eq!(
+            read(&content),
+            [
+                (None, "pool,CBADADDR".to_string()),
+                (None, CONTRACT.to_string()),
+            ]
+        );
+    }
+}
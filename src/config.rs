@@ -1,10 +1,29 @@
-use crate::{
-    network_config::{url_for_network, UrlType},
-    runner::Runner,
-    Error,
+use admin_checker::{Duration, Error, Runner, Since, ThresholdLevel, DEFAULT_MAX_TXS};
+use clap::{Parser, ValueEnum};
+use std::{
+    env,
+    path::{Path, PathBuf},
 };
-use clap::Parser;
-use std::env;
+
+/// Common privileged role names, used with `--all-roles`
+const PRESET_ROLES: &[&str] = &[
+    "admin",
+    "owner",
+    "pauser",
+    "minter",
+    "upgrader",
+    "manager",
+    "operator",
+    "governance",
+];
+
+/// Output format of the analysis
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum OutputFormat {
+    Text,
+    Json,
+    Csv,
+}
 
 #[derive(Parser, Debug)]
 #[command(version = "0.1", about = "Checks admin wallet type")]
@@ -14,7 +33,7 @@ pub struct Config {
     #[arg(
         short,
         long,
-        conflicts_with_all(&["contract_id", "key"]),
+        conflicts_with_all(&["contract_id", "key", "raw_key"]),
         help("Admin key to search for. When used, all the other options are ignored")
     )]
     admin: Option<String>,
@@ -30,15 +49,86 @@ pub struct Config {
     #[arg(
         short,
         long,
-        default_value("admin"),
-        help("Admin's storage slot key to search for. Defaults to 'admin'")
+        help("Privileged role's storage slot key to search for. Can be repeated to resolve several roles. Enum variants with arguments are written as 'Role:GABC...' or 'Admins:0'. At most 32 characters, like a Soroban symbol. Defaults to 'admin'")
+    )]
+    key: Vec<String>,
+    #[arg(
+        long,
+        help("Raw storage key to search for, as base64 XDR or JSON of an ScVal. Used as is, without case variations. Can be repeated")
     )]
-    key: String,
+    raw_key: Vec<String>,
     #[arg(
         long,
-        help("Horizon URL to use. If not provided, it will be inferred from the network")
+        conflicts_with("admin"),
+        help("Also resolve the common privileged roles: admin, owner, pauser, minter, upgrader, manager, operator and governance")
+    )]
+    all_roles: bool,
+    #[arg(
+        long,
+        help("Horizon URL to use for the transaction history of EOA admins. If not provided, it will be inferred from the network. Signers and thresholds are always read through RPC")
     )]
     horizon: Option<String>,
+    #[arg(
+        long,
+        default_value_t = 5,
+        help("Maximum length of the admin chain to follow when the admin is a contract. Defaults to 5")
+    )]
+    max_depth: usize,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = OutputFormat::Text,
+        help("Output format. Defaults to 'text'")
+    )]
+    format: OutputFormat,
+    #[arg(
+        short,
+        long,
+        conflicts_with_all(&["contract_id", "admin"]),
+        help("File with the contract IDs or G-addresses to analyze, one per line. Lines can also be CSV with a label next to the address, quoted if it contains commas")
+    )]
+    input: Option<PathBuf>,
+    #[arg(
+        long,
+        default_value_t = 4,
+        help("Maximum number of targets analyzed at the same time in batch mode. Defaults to 4")
+    )]
+    concurrency: usize,
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = ThresholdLevel::Medium,
+        help("Threshold level EOA admins are classified against. Contract invocations check 'medium', changing signers checks 'high'. Defaults to 'medium'")
+    )]
+    threshold: ThresholdLevel,
+    #[arg(
+        long,
+        conflicts_with("admin"),
+        help("Scan the whole instance storage for addresses and rank them by key name. The best one is used as the admin if its name is admin-like enough, otherwise 'key' is looked up as usual. Other roles and raw keys are always looked up by key. Nested admin contracts are always searched for their 'admin' key")
+    )]
+    discover: bool,
+    #[arg(
+        long,
+        default_value_t = DEFAULT_MAX_TXS,
+        help("Maximum number of most recent transactions analyzed for single-signer admins, 0 for no limit. Defaults to 1000")
+    )]
+    max_txs: usize,
+    #[arg(
+        long,
+        help("Only analyze transactions from this ledger number, date (YYYY-MM-DD) or RFC 3339 time on")
+    )]
+    since: Option<Since>,
+    #[arg(
+        long,
+        help("Only analyze transactions within this length of time before now, e.g. '30d', '12h' or '2w'")
+    )]
+    window: Option<Duration>,
+    #[arg(
+        long,
+        default_value = "1m",
+        help("Longest gap between two transactions for them to count as back-to-back, e.g. '30s' or '2m'. Measured between ledger close times. Defaults to '1m'")
+    )]
+    back_to_back: Duration,
 }
 
 impl Config {
@@ -57,13 +147,28 @@ impl Config {
             config.network = env::var("SOROBAN_NETWORK").ok()
         }
 
-        if config.admin.is_none() && config.contract_id.is_none() {
+        if config.admin.is_none() && config.contract_id.is_none() && config.input.is_none() {
             return Err(Error::MissingTargetAddress);
         }
 
         Ok(config)
     }
 
+    /// Returns the selected output format
+    pub fn format(&self) -> OutputFormat {
+        self.format
+    }
+
+    /// Returns the batch input file, if any
+    pub fn input(&self) -> Option<&Path> {
+        self.input.as_deref()
+    }
+
+    /// Returns the maximum number of concurrent analyses in batch mode
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
     /// Creates a Runner instance with the current configuration
     pub fn to_runner(&self) -> Result<Runner, Error> {
         let contract_id = if self.admin.is_some() {
@@ -73,47 +178,55 @@ impl Config {
         }
         .ok_or(Error::MissingTargetAddress)?;
 
-        if self.network.is_none() && self.horizon.is_none() {
-            return Err(Error::HorizonUrlNotAvailable);
-        }
-
-        let network = if let Some(n) = &self.network {
-            Ok(n.clone())
-        } else {
-            env::var("SOROBAN_NETWORK").map_err(|_| Error::MissingNetwork)
-        };
-
-        let rpc_url = self.get_url(&network, UrlType::Rpc)?;
-        let horizon_url = self.get_url(&network, UrlType::Horizon)?;
-
-        Runner::new(&rpc_url, horizon_url, &contract_id, &self.key)
+        self.to_runner_for(&contract_id)
     }
 
-    /// Gets the appropriate URL for the specified network and URL type.
+    /// Creates a Runner instance for the given target with the current configuration
     ///
     /// # Arguments
-    /// * `network` - The network configuration
-    /// * `url_type` - The type of URL to retrieve
-    fn get_url(&self, network: &Result<String, Error>, url_type: UrlType) -> Result<String, Error> {
-        let mut url = match url_type {
-            UrlType::Rpc => {
-                if let Some(url) = &self.rpc_url {
-                    url.clone()
-                } else {
-                    url_for_network(&network.clone()?, UrlType::Rpc)?
-                }
-            }
-            UrlType::Horizon => {
-                if let Some(url) = &self.horizon {
-                    url.clone()
-                } else {
-                    url_for_network(&network.clone()?, UrlType::Horizon)?
-                }
+    /// * `contract_id` - The contract ID or G-address to analyze
+    pub fn to_runner_for(&self, contract_id: &str) -> Result<Runner, Error> {
+        let mut builder = Runner::builder(contract_id)
+            .keys(self.roles())
+            .raw_keys(self.raw_key.clone())
+            .max_depth(self.max_depth)
+            .threshold_level(self.threshold)
+            .discover(self.discover)
+            .max_txs((self.max_txs > 0).then_some(self.max_txs))
+            .back_to_back(self.back_to_back);
+        if let Some(since) = self.since {
+            builder = builder.since(since);
+        }
+        if let Some(window) = self.window {
+            builder = builder.window(window);
+        }
+        if let Some(rpc_url) = &self.rpc_url {
+            builder = builder.rpc_url(rpc_url);
+        }
+        if let Some(network) = &self.network {
+            builder = builder.network(network);
+        }
+        if let Some(horizon) = &self.horizon {
+            builder = builder.horizon_url(horizon);
+        }
+        builder.build()
+    }
+
+    /// Gets the roles to resolve, without duplicates and in the order given.
+    /// The runner falls back to 'admin' when no key is given at all.
+    fn roles(&self) -> Vec<String> {
+        let mut roles: Vec<String> = Vec::new();
+        let preset = PRESET_ROLES.iter().map(|r| r.to_string());
+        let all = self
+            .key
+            .iter()
+            .cloned()
+            .chain(preset.filter(|_| self.all_roles));
+        for role in all {
+            if !roles.contains(&role) {
+                roles.push(role);
             }
-        };
-        if !url.ends_with('/') {
-            url.push('/');
         }
-        Ok(url)
+        roles
     }
 }
@@ -0,0 +1,102 @@
+use serde::Serialize;
+use stellar_xdr::curr::{ScMapEntry, ScVal};
+
+use crate::{
+    role_hints::key_score,
+    storage_helper::{decode_admin_value, key_name, AddressType, ValueAddress},
+};
+
+/// Minimum score for a candidate to be taken as the admin without a key lookup
+pub const MIN_SELECT_SCORE: u32 = 50;
+
+/// Address-valued instance storage entry, ranked by how likely it is to be the admin.
+#[derive(Debug, Clone, Serialize)]
+pub struct Candidate {
+    /// Storage key of the entry
+    pub key: ScVal,
+    /// Readable name of the key, if it is a symbol, string or enum variant
+    pub key_name: Option<String>,
+    /// Address stored in the entry, the most admin-like one if there are several
+    pub address: AddressType,
+    /// Every address found in the entry's value
+    pub addresses: Vec<ValueAddress>,
+    /// Heuristic score of the key name, higher is more likely to be the admin
+    pub score: u32,
+}
+
+/// Collects every instance storage entry holding addresses, best candidates first.
+///
+/// # Arguments
+/// * `entries` - The contract's instance storage
+pub fn rank_candidates(entries: &[ScMapEntry]) -> Vec<Candidate> {
+    let mut candidates: Vec<Candidate> = entries
+        .iter()
+        .filter_map(|entry| {
+            let decoded = decode_admin_value(&entry.val).ok()?;
+            let key_name = key_name(&entry.key);
+            Some(Candidate {
+                score: key_name.as_deref().map_or(0, key_score),
+                key: entry.key.clone(),
+                key_name,
+                address: decoded.admin,
+                addresses: decoded.addresses,
+            })
+        })
+        .collect();
+
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.score));
+    candidates
+}
+
+/// Returns the best ranked candidate, if its key name is admin-like enough to be taken as
+/// the admin. Otherwise the admin is looked up by key and the candidates are only listed.
+///
+/// # Arguments
+/// * `candidates` - Candidates ranked by `rank_candidates`
+pub fn select_candidate(candidates: &[Candidate]) -> Option<&Candidate> {
+    candidates.first().filter(|c| c.score >= MIN_SELECT_SCORE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stellar_xdr::curr::{Hash, ScAddress, ScSymbol, StringM};
+
+    fn entry(key: &str, contract: u8) -> ScMapEntry {
+        ScMapEntry {
+            key: ScVal::Symbol(ScSymbol::from(StringM::try_from(key).unwrap())),
+            val: ScVal::Address(ScAddress::Contract(Hash([contract; 32]))),
+        }
+    }
+
+    #[test]
+    fn ranks_admin_like_keys_first() {
+        let candidates = rank_candidates(&[
+            entry("Token", 1),
+            entry("Pauser", 2),
+            entry("OwnerAddress", 3),
+            entry("Admin", 4),
+        ]);
+
+        let keys: Vec<_> = candidates.iter().map(|c| c.key_name.clone()).collect();
+        assert_eq!(
+            keys,
+            ["Admin", "OwnerAddress", "Pauser", "Token"].map(|k| Some(k.to_string()))
+        );
+        assert_eq!(candidates[3].score, 0);
+        assert_eq!(
+            select_candidate(&candidates).and_then(|c| c.key_name.as_deref()),
+            Some("Admin")
+        );
+    }
+
+    #[test]
+    fn does_not_select_unrelated_addresses() {
+        let candidates = rank_candidates(&[entry("Token", 1), entry("FeeRecipient", 2)]);
+        assert_eq!(candidates.len(), 2);
+        assert!(select_candidate(&candidates).is_none());
+
+        let candidates = rank_candidates(&[entry("Minter", 1)]);
+        assert!(select_candidate(&candidates).is_none());
+    }
+}
@@ -0,0 +1,93 @@
+use chrono::TimeDelta;
+use core::fmt;
+use serde::{Serialize, Serializer};
+use std::str::FromStr;
+
+use crate::error::Error;
+
+/// Units a duration can be written in, largest first, with their length in seconds
+const UNITS: [(char, i64); 5] = [
+    ('w', 7 * 24 * 3600),
+    ('d', 24 * 3600),
+    ('h', 3600),
+    ('m', 60),
+    ('s', 1),
+];
+
+/// Length of time, written as a number followed by `s`, `m`, `h`, `d` or `w`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Duration(pub TimeDelta);
+
+impl Duration {
+    /// Creates a duration of the given number of seconds
+    pub fn seconds(secs: i64) -> Self {
+        Duration(TimeDelta::seconds(secs))
+    }
+}
+
+/// Serialized as a number of seconds
+impl Serialize for Duration {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_i64(self.0.num_seconds())
+    }
+}
+
+impl FromStr for Duration {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = || Error::MalformedDuration(s.to_string());
+        let unit = s.chars().last().ok_or_else(malformed)?;
+        let secs = UNITS
+            .iter()
+            .find(|(u, _)| *u == unit)
+            .map(|(_, secs)| *secs)
+            .ok_or_else(malformed)?;
+        let count: i64 = s[..s.len() - 1].parse().map_err(|_| malformed())?;
+        if count < 0 {
+            return Err(malformed());
+        }
+        count
+            .checked_mul(secs)
+            .and_then(TimeDelta::try_seconds)
+            .map(Duration)
+            .ok_or_else(malformed)
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let secs = self.0.num_seconds();
+        // Largest unit the duration is a whole number of
+        let (unit, size) = UNITS
+            .iter()
+            .find(|(_, size)| secs != 0 && secs % size == 0)
+            .unwrap_or(&('s', 1));
+        write!(f, "{}{}", secs / size, unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!("30d".parse::<Duration>().unwrap().0, TimeDelta::days(30));
+        assert_eq!("0s".parse::<Duration>().unwrap(), Duration::seconds(0));
+        assert_eq!("0s".parse::<Duration>().unwrap().to_string(), "0s");
+        assert_eq!("12h".parse::<Duration>().unwrap().to_string(), "12h");
+        assert_eq!("14d".parse::<Duration>().unwrap().to_string(), "2w");
+        assert_eq!(Duration::seconds(90).to_string(), "90s");
+        for malformed in ["", "d", "30", "-1d", "1.5h", "1y"] {
+            assert!(
+                matches!(
+                    malformed.parse::<Duration>(),
+                    Err(Error::MalformedDuration(_))
+                ),
+                "{}",
+                malformed
+            );
+        }
+    }
+}
@@ -1,24 +1,67 @@
+use std::{path::PathBuf, sync::Arc};
 use thiserror::Error;
 
+type RpcError = stellar_rpc_client::Error;
+
+/// Errors returned by the analysis
 #[derive(Debug, Error, Clone)]
 #[allow(clippy::enum_variant_names)]
-pub(crate) enum Error {
-    #[error("multiple potential admin addresses")]
-    MultipleAdminsFound,
+#[non_exhaustive]
+pub enum Error {
     #[error("wrong storage type")]
     WrongStorageType,
     #[error("malformed address")]
     MalformedAddress,
-    #[error("malformed rpc url")]
-    MalformedUrl,
+    #[error("malformed key expression {0}")]
+    MalformedKey(String),
+    #[error("key {0} is longer than the 32 characters allowed in a Soroban symbol")]
+    KeyTooLong(String),
+    #[error(
+        "malformed date or ledger {0}, expected a ledger number, YYYY-MM-DD or an RFC 3339 time"
+    )]
+    MalformedSince(String),
+    #[error("malformed duration {0}, expected a number followed by s, m, h, d or w")]
+    MalformedDuration(String),
+    #[error("malformed rpc url {url}: {source}")]
+    MalformedUrl {
+        url: String,
+        #[source]
+        source: Arc<RpcError>,
+    },
+    #[error("malformed rpc response: {0}")]
+    MalformedRpcResponse(String),
     #[error("admin not found")]
     AdminNotFound,
+    #[error("failed to fetch account from {url}: {}", chain(.source))]
+    AccountFetchFailure {
+        url: String,
+        #[source]
+        source: Arc<RpcError>,
+    },
+    #[error("account {0} does not exist on-chain")]
+    AccountNotFound(String),
     #[error("target address is not a contract")]
     NotAContract,
-    #[error("failed to fetch instance storage")]
-    InstanceStorageFailure,
-    #[error("failed to fetch persistent storage")]
-    PersistentStorageFailure,
+    #[error("contract {0} not found")]
+    ContractNotFound(String),
+    #[error("failed to fetch instance storage from {url}: {}", chain(.source))]
+    InstanceStorageFailure {
+        url: String,
+        #[source]
+        source: Arc<RpcError>,
+    },
+    #[error("failed to fetch persistent storage from {url}: {}", chain(.source))]
+    PersistentStorageFailure {
+        url: String,
+        #[source]
+        source: Arc<RpcError>,
+    },
+    #[error("failed to fetch temporary storage from {url}: {}", chain(.source))]
+    TemporaryStorageFailure {
+        url: String,
+        #[source]
+        source: Arc<RpcError>,
+    },
     #[error("unknown network")]
     InvalidNetwork,
     #[error("failed to load config")]
@@ -31,14 +74,55 @@ pub(crate) enum Error {
     RpcUrlNotSet,
     #[error("failed to parse toml")]
     TomlParseFailure,
-    #[error("Contract id or admin is missing")]
+    #[error("Contract id, admin or input file is missing")]
     MissingTargetAddress,
     #[error("missing network")]
     MissingNetwork,
     #[error("cannot find horizon url")]
     HorizonUrlNotAvailable,
-    #[error("failed to fetch horizon data")]
-    HorizonDataFetchFailure,
-    #[error("failed to parse horizon data json")]
-    HorizonDataParseFailure,
+    #[error("failed to fetch horizon data from {url}: {}", chain(.source))]
+    HorizonDataFetchFailure {
+        url: String,
+        #[source]
+        source: Arc<reqwest::Error>,
+    },
+    #[error("horizon returned HTTP {status} for {url}{}", .detail.as_ref().map(|d| format!(": {}", d)).unwrap_or_default())]
+    HorizonStatus {
+        url: String,
+        status: u16,
+        /// Title and detail of the problem JSON returned by Horizon, if any
+        detail: Option<String>,
+    },
+    #[error("failed to parse horizon data json from {url}: {}", chain(.source))]
+    HorizonDataParseFailure {
+        url: String,
+        #[source]
+        source: Arc<reqwest::Error>,
+    },
+    #[error("failed to serialize report")]
+    ReportSerializationFailure,
+    #[error("failed to read input file {}: {source}", .path.display())]
+    InputLoadFailure {
+        path: PathBuf,
+        #[source]
+        source: Arc<std::io::Error>,
+    },
+    #[error("analysis task aborted")]
+    AnalysisAborted,
+}
+
+/// Formats an error followed by its sources, as transport errors often only name the
+/// actual cause (DNS, TLS, timeout, ...) in their source.
+fn chain(err: &dyn std::error::Error) -> String {
+    let mut message = err.to_string();
+    let mut source = err.source();
+    while let Some(e) = source {
+        // Some errors already repeat their source in their own message
+        let part = e.to_string();
+        if !message.contains(&part) {
+            message.push_str(&format!(": {}", part));
+        }
+        source = e.source();
+    }
+    message
 }
@@ -0,0 +1,198 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use core::fmt;
+use serde::Serialize;
+use std::str::FromStr;
+
+use crate::{duration::Duration, error::Error};
+
+/// Number of most recent transactions analyzed by default
+pub const DEFAULT_MAX_TXS: usize = 1000;
+
+/// Oldest point of the transaction history to fetch.
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Since {
+    /// Ledger sequence number
+    Ledger(u64),
+    /// Close time, a date being taken at midnight UTC
+    Time(DateTime<Utc>),
+}
+
+impl FromStr for Since {
+    type Err = Error;
+
+    /// Parses a ledger number, a `YYYY-MM-DD` date or an RFC 3339 timestamp.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(ledger) = s.parse() {
+            return Ok(Since::Ledger(ledger));
+        }
+        if let Ok(date) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
+            return Ok(Since::Time(date.and_time(Default::default()).and_utc()));
+        }
+        DateTime::parse_from_rfc3339(s)
+            .map(|t| Since::Time(t.to_utc()))
+            .map_err(|_| Error::MalformedSince(s.to_string()))
+    }
+}
+
+impl fmt::Display for Since {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Since::Ledger(ledger) => write!(f, "ledger {}", ledger),
+            Since::Time(time) => write!(f, "{}", time.format("%Y-%m-%d %H:%M:%S UTC")),
+        }
+    }
+}
+
+/// Why the analyzed transaction history stops before the account's first transaction.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Truncation {
+    /// The maximum number of transactions was reached
+    MaxTxs(usize),
+    /// Older transactions are before `--since`
+    Since(Since),
+    /// Older transactions are outside of `--window`, serialized as a number of seconds
+    Window(Duration),
+    /// Older transactions could no longer change the verdict
+    Settled,
+}
+
+impl fmt::Display for Truncation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Truncation::MaxTxs(max) => write!(f, "limited to the {} most recent transactions", max),
+            Truncation::Since(since) => write!(f, "limited to transactions since {}", since),
+            Truncation::Window(window) => write!(f, "limited to the last {}", window),
+            Truncation::Settled => write!(
+                f,
+                "stopped once older transactions could no longer change the verdict"
+            ),
+        }
+    }
+}
+
+/// Bounds of the transaction history to fetch, newest transactions first.
+#[derive(Debug, Clone)]
+pub struct HistoryBounds {
+    /// Maximum number of transactions, `None` for no limit
+    pub max_txs: Option<usize>,
+    /// Oldest ledger or close time to fetch
+    pub since: Option<Since>,
+    /// Length of time before now to fetch
+    pub window: Option<Duration>,
+}
+
+impl Default for HistoryBounds {
+    fn default() -> Self {
+        Self {
+            max_txs: Some(DEFAULT_MAX_TXS),
+            since: None,
+            window: None,
+        }
+    }
+}
+
+impl HistoryBounds {
+    /// Checks whether a transaction falls outside of the bounds, returning why.
+    ///
+    /// # Arguments
+    /// * `ledger` - Ledger the transaction was included in
+    /// * `created_at` - Close time of that ledger
+    /// * `now` - Time the window ends at
+    pub fn excludes(
+        &self,
+        ledger: u64,
+        created_at: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Option<Truncation> {
+        let before_since = match self.since {
+            Some(Since::Ledger(since)) => ledger < since,
+            Some(Since::Time(since)) => created_at < since,
+            None => false,
+        };
+        if before_since {
+            return self.since.map(Truncation::Since);
+        }
+        // A window reaching past the earliest representable time has no lower bound
+        let start = self
+            .window
+            .and_then(|w| Some((w, now.checked_sub_signed(w.0)?)));
+        match start {
+            Some((window, start)) if created_at < start => Some(Truncation::Window(window)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeDelta, TimeZone};
+
+    #[test]
+    fn parses_since() {
+        assert!(matches!("52000000".parse(), Ok(Since::Ledger(52000000))));
+
+        let midnight = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+        assert!(matches!("2024-03-01".parse(), Ok(Since::Time(t)) if t == midnight));
+        assert!(matches!(
+            "2024-03-01T02:00:00+02:00".parse(),
+            Ok(Since::Time(t)) if t == midnight
+        ));
+        assert!(matches!(
+            "last week".parse::<Since>(),
+            Err(Error::MalformedSince(_))
+        ));
+    }
+
+    #[test]
+    fn excludes_transactions_out_of_bounds() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 10, 0, 0, 0).unwrap();
+        let bounds = HistoryBounds {
+            max_txs: None,
+            since: Some(Since::Ledger(100)),
+            window: Some("7d".parse().unwrap()),
+        };
+
+        assert!(bounds.excludes(150, now, now).is_none());
+        assert!(matches!(
+            bounds.excludes(99, now, now),
+            Some(Truncation::Since(_))
+        ));
+        assert!(matches!(
+            bounds.excludes(150, now - TimeDelta::days(8), now),
+            Some(Truncation::Window(_))
+        ));
+    }
+
+    #[test]
+    fn window_past_the_earliest_time_has_no_bound() {
+        let now = Utc.with_ymd_and_hms(2024, 3, 10, 0, 0, 0).unwrap();
+        let bounds = HistoryBounds {
+            max_txs: None,
+            since: None,
+            window: Some("20000000w".parse().unwrap()),
+        };
+        assert!(bounds.excludes(1, DateTime::<Utc>::MIN_UTC, now).is_none());
+    }
+
+    #[test]
+    fn serializes_truncation_as_data() {
+        let since = Since::Time(Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap());
+        assert_eq!(
+            serde_json::to_value(Truncation::Since(since)).unwrap(),
+            serde_json::json!({"since": {"time": "2024-03-01T00:00:00Z"}})
+        );
+        assert_eq!(
+            serde_json::to_value(Truncation::Since(Since::Ledger(100))).unwrap(),
+            serde_json::json!({"since": {"ledger": 100}})
+        );
+        let window = Truncation::Window("7d".parse().unwrap());
+        assert_eq!(
+            serde_json::to_value(&window).unwrap(),
+            serde_json::json!({"window": 604800})
+        );
+        assert_eq!(window.to_string(), "limited to the last 1w");
+    }
+}
@@ -1,25 +1,20 @@
-use crate::{account_type::AccountType, error::Error};
-use serde::Deserialize;
-
-#[derive(Deserialize, Debug)]
-struct Thresholds {
-    pub low_threshold: u8,
-}
-
-#[derive(Deserialize, Debug)]
-struct Signer {
-    pub weight: u8,
-}
-
-#[derive(Deserialize, Debug)]
-struct AccountData {
-    pub thresholds: Thresholds,
-    pub signers: Vec<Signer>,
-}
+use crate::{
+    error::Error,
+    history::{HistoryBounds, Truncation},
+    tx_pattern::{HistoryAnalyzer, TxFrequency},
+};
+use chrono::{DateTime, TimeDelta, Utc};
+use futures_util::{stream, Stream, TryStreamExt};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{pin::pin, sync::Arc, time::SystemTime};
 
+/// Transaction as listed in an account's history.
 #[derive(Deserialize, Debug, Clone)]
 struct TxRecord {
+    /// Ledger the transaction was included in
     pub ledger: u64,
+    /// Close time of that ledger
+    pub created_at: DateTime<Utc>,
     pub paging_token: String,
     pub source_account: String,
     pub fee_account: String,
@@ -46,133 +41,286 @@ struct Wrapper {
     pub _embedded: Response,
 }
 
-/// Checks if a "G" account is a multisig/"decentralized" account by analyzing its signers and thresholds.
+/// Problem JSON returned by Horizon on errors
+#[derive(Deserialize, Debug)]
+struct Problem {
+    pub title: Option<String>,
+    pub detail: Option<String>,
+}
+
+/// Fetches and parses a Horizon resource.
 ///
-/// Returns:
-/// - `AccountType::HotWallet` if any single signer has enough weight for transactions
-/// - `AccountType::Multisig` if multiple signers are required
-/// - `AccountType::Deactivated` if the account has no active signers
+/// Non-success responses are turned into `Error::HorizonStatus`, carrying the
+/// title and detail of the problem JSON when Horizon returns one.
 ///
 /// # Arguments
-/// * `horizon_url` - The base URL of the Horizon API
-/// * `account_id` - The Stellar account ID to check
-pub async fn check_if_centralized(
-    horizon_url: String,
-    account_id: String,
-) -> Result<AccountType, Error> {
-    let url = format!("{}accounts/{}/", horizon_url, account_id);
-    let response = reqwest::get(&url)
+/// * `url` - The full URL of the resource
+async fn horizon_get<T: DeserializeOwned>(url: &str) -> Result<T, Error> {
+    let response = reqwest::get(url)
         .await
-        .map_err(|_| Error::HorizonDataFetchFailure)?;
+        .map_err(|e| Error::HorizonDataFetchFailure {
+            url: url.to_string(),
+            source: Arc::new(e),
+        })?;
 
-    if !response.status().is_success() {
-        return Err(Error::HorizonDataFetchFailure);
+    let status = response.status();
+    if !status.is_success() {
+        let detail =
+            response
+                .json::<Problem>()
+                .await
+                .ok()
+                .and_then(|p| match (p.title, p.detail) {
+                    (Some(title), Some(detail)) => Some(format!("{} ({})", title, detail)),
+                    (title, detail) => title.or(detail),
+                });
+        return Err(Error::HorizonStatus {
+            url: url.to_string(),
+            status: status.as_u16(),
+            detail,
+        });
     }
 
-    let body: AccountData = response
+    response
         .json()
         .await
-        .map_err(|_| Error::HorizonDataParseFailure)?;
-
-    let mut weights: Vec<u8> = body.signers.iter().map(|s| s.weight).collect();
-    let max_weight = *weights.iter().max().unwrap_or(&0);
-
-    if max_weight == 0 {
-        return Ok(AccountType::Deactivated);
-    }
-
-    if max_weight >= body.thresholds.low_threshold {
-        return Ok(AccountType::HotWallet);
-    }
-
-    // Determine multisig account type
-    let total_signers = weights.iter().filter(|&&x| x > 0).count();
-    weights.sort_unstable_by(|a, b| b.cmp(a));
-
-    let mut total_weight = 0;
-    for i in 0..total_signers {
-        total_weight += weights[i];
-        if total_weight >= body.thresholds.low_threshold {
-            return Ok(AccountType::Multisig(i as u8 + 1, total_signers as u8));
-        }
-    }
-
-    Ok(AccountType::Deactivated)
+        .map_err(|e| Error::HorizonDataParseFailure {
+            url: url.to_string(),
+            source: Arc::new(e),
+        })
 }
 
-/// Calculates the minimum time between transactions for an account.
+/// Analyzes the transaction pattern of an account.
 ///
-/// Returns the minimum number of ledgers between any two consecutive transactions.
-/// Returns `u64::MAX` if the account has fewer than 2 transactions.
+/// Returns the distribution of the gaps between consecutive transactions, bursts,
+/// and the time of day and weekday spread of the account's most recent transactions,
+/// within the given bounds. Paging stops early once older transactions can no longer
+/// change the verdict.
 ///
 /// # Arguments
 /// * `horizon_url` - The base URL of the Horizon API
 /// * `account_id` - The Stellar account ID to analyze
+/// * `bounds` - How much of the history to fetch
+/// * `back_to_back` - Gap up to which consecutive transactions are back-to-back
 pub async fn tx_frequency_for_account(
     horizon_url: String,
     account_id: String,
-) -> Result<u64, Error> {
-    let txs = get_all_txs_for_account(horizon_url.clone(), account_id.clone()).await?;
+    bounds: &HistoryBounds,
+    back_to_back: TimeDelta,
+) -> Result<TxFrequency, Error> {
+    let now = DateTime::<Utc>::from(SystemTime::now());
+    analyze_pages(
+        tx_pages(&horizon_url, &account_id),
+        &account_id,
+        bounds,
+        back_to_back,
+        now,
+    )
+    .await
+}
 
-    if txs.len() < 2 {
-        return Ok(u64::MAX);
-    }
+/// Feeds pages of transactions, newest first, to a `HistoryAnalyzer`.
+///
+/// Stops pulling pages at the first transaction out of the bounds, or once older
+/// transactions can no longer change the verdict.
+///
+/// # Arguments
+/// * `pages` - Pages of the account's transactions, newest first
+/// * `account_id` - The account, transactions it neither sent nor paid for are skipped
+/// * `bounds` - How much of the history to analyze
+/// * `back_to_back` - Gap up to which consecutive transactions are back-to-back
+/// * `now` - Time the `--window` bound ends at
+async fn analyze_pages<S>(
+    pages: S,
+    account_id: &str,
+    bounds: &HistoryBounds,
+    back_to_back: TimeDelta,
+    now: DateTime<Utc>,
+) -> Result<TxFrequency, Error>
+where
+    S: Stream<Item = Result<Vec<TxRecord>, Error>>,
+{
+    let mut analyzer = HistoryAnalyzer::new(bounds.max_txs, back_to_back);
+    let mut last_token: Option<String> = None;
+    let mut pages = pin!(pages);
+
+    let truncated = 'paging: loop {
+        let Some(page) = pages.try_next().await? else {
+            break None;
+        };
+        for tx in page {
+            if tx.source_account != account_id && tx.fee_account != account_id {
+                continue;
+            }
+            // Pages may overlap, a repeated record is not a transaction left out
+            if last_token.as_ref() == Some(&tx.paging_token) {
+                continue;
+            }
+            if let Some(truncation) = bounds.excludes(tx.ledger, tx.created_at, now) {
+                break 'paging Some(truncation);
+            }
+            if analyzer.is_full() {
+                break 'paging bounds.max_txs.map(Truncation::MaxTxs);
+            }
+            analyzer.push(tx.created_at);
+            last_token = Some(tx.paging_token);
+        }
+        // A full analyzer still fetches the next page, to tell whether anything was left out
+        if !analyzer.is_full() && analyzer.is_settled() {
+            break Some(Truncation::Settled);
+        }
+    };
 
-    let min_ledger_dif = txs
-        .windows(2)
-        .map(|r| r[1].ledger - r[0].ledger)
-        .min()
-        .unwrap();
+    let mut frequency = analyzer.finish();
+    frequency.truncated = truncated;
+    Ok(frequency)
+}
 
-    Ok(min_ledger_dif)
+/// Streams the pages of an account's transactions, newest first.
+///
+/// Pages are only fetched when polled, so dropping the stream stops paging.
+/// The stream ends at the first empty page.
+///
+/// # Arguments
+/// * `horizon_url` - The base URL of the Horizon API
+/// * `account_id` - The Stellar account ID whose transactions to list
+fn tx_pages(
+    horizon_url: &str,
+    account_id: &str,
+) -> impl Stream<Item = Result<Vec<TxRecord>, Error>> {
+    let first = format!(
+        "{}accounts/{}/transactions?limit=200&order=desc&include_failed=false",
+        horizon_url, account_id
+    );
+    stream::try_unfold(Some(first), |url| async move {
+        let Some(url) = url else {
+            return Ok(None);
+        };
+        let body: Wrapper = horizon_get(&url).await?;
+        if body._embedded.records.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some((body._embedded.records, Some(body._links.next.href))))
+    })
 }
 
-async fn get_all_txs_for_account(
-    horizon_url: String,
-    account_id: String,
-) -> Result<Vec<TxRecord>, Error> {
-    let mut results = Vec::new();
-    let mut next_url = None;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::Since;
+    use chrono::TimeZone;
+    use futures_util::StreamExt;
+    use std::cell::Cell;
 
-    loop {
-        let (txs, next_url_) =
-            get_txs_from_cursor(horizon_url.clone(), account_id.clone(), next_url).await?;
-        results.extend(txs.clone());
+    const ACCOUNT: &str = "GA";
 
-        if txs.len() != 200 {
-            break;
-        }
+    fn start() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+    }
 
-        next_url = Some(next_url_);
+    /// Transactions of the account in the given ledgers, newest first, with 5 second ledgers
+    fn txs(ledgers: impl Iterator<Item = u64>) -> Vec<TxRecord> {
+        let mut txs: Vec<TxRecord> = ledgers
+            .map(|ledger| TxRecord {
+                ledger,
+                created_at: start() + TimeDelta::seconds(ledger as i64 * 5),
+                paging_token: ledger.to_string(),
+                source_account: ACCOUNT.to_string(),
+                fee_account: ACCOUNT.to_string(),
+            })
+            .collect();
+        txs.reverse();
+        txs
     }
 
-    results.retain(|r| r.source_account == account_id || r.fee_account == account_id);
-    results.dedup_by_key(|r| r.paging_token.clone());
-    Ok(results)
-}
+    /// Analyzes in-memory pages, returning the figures and the number of pages pulled
+    async fn analyze(pages: Vec<Vec<TxRecord>>, bounds: &HistoryBounds) -> (TxFrequency, usize) {
+        let pulled = Cell::new(0);
+        let pages =
+            stream::iter(pages.into_iter().map(Ok)).inspect(|_| pulled.set(pulled.get() + 1));
+        let now = start() + TimeDelta::days(30);
+        let frequency = analyze_pages(pages, ACCOUNT, bounds, TimeDelta::seconds(60), now)
+            .await
+            .unwrap();
+        (frequency, pulled.get())
+    }
 
-async fn get_txs_from_cursor(
-    horizon_url: String,
-    account_id: String,
-    url_: Option<String>,
-) -> Result<(Vec<TxRecord>, String), Error> {
-    let url = url_.unwrap_or(format!(
-        "{}accounts/{}/transactions?limit=200&include_failed=false",
-        horizon_url, account_id
-    ));
+    fn bounds(max_txs: Option<usize>) -> HistoryBounds {
+        HistoryBounds {
+            max_txs,
+            ..Default::default()
+        }
+    }
 
-    let response = reqwest::get(&url)
-        .await
-        .map_err(|_| Error::HorizonDataFetchFailure)?;
+    #[tokio::test]
+    async fn skips_other_accounts_and_stops_at_bounds() {
+        let mut page = txs(100..110);
+        page[0].source_account = "GB".to_string();
+        page[0].fee_account = "GB".to_string();
+        let bounds = HistoryBounds {
+            since: Some(Since::Ledger(105)),
+            ..bounds(None)
+        };
 
-    if !response.status().is_success() {
-        return Err(Error::HorizonDataFetchFailure);
+        let (frequency, pulled) = analyze(vec![page, txs(0..10)], &bounds).await;
+        assert_eq!(frequency.tx_count, 4);
+        assert!(matches!(frequency.truncated, Some(Truncation::Since(_))));
+        assert_eq!(pulled, 1);
     }
 
-    let body: Wrapper = response
-        .json()
-        .await
-        .map_err(|_| Error::HorizonDataParseFailure)?;
+    #[tokio::test]
+    async fn reports_the_limit_only_when_transactions_are_left_out() {
+        // A transaction a day, which does not settle the verdict early
+        let daily = |days: std::ops::Range<u64>| txs(days.map(|d| d * 17_280));
+        let pages = vec![daily(10..20), daily(0..10)];
+        let (frequency, _) = analyze(pages, &bounds(Some(20))).await;
+        assert_eq!(frequency.tx_count, 20);
+        assert!(frequency.truncated.is_none());
+
+        let (frequency, _) = analyze(vec![daily(0..20)], &bounds(Some(15))).await;
+        assert_eq!(frequency.tx_count, 15);
+        assert!(matches!(frequency.truncated, Some(Truncation::MaxTxs(15))));
 
-    Ok((body._embedded.records, body._links.next.href))
+        // The last record of a page repeated at the top of the next one
+        let mut second = daily(0..10);
+        second.insert(0, daily(10..11).remove(0));
+        let (frequency, _) = analyze(vec![daily(10..20), second], &bounds(Some(20))).await;
+        assert_eq!(frequency.tx_count, 20);
+        assert!(frequency.truncated.is_none());
+    }
+
+    #[tokio::test]
+    async fn stops_paging_once_the_verdict_is_settled() {
+        // Bursts of 4 transactions about every hour, in pages of 200
+        let ledgers: Vec<u64> = (0..1000).map(|i| i / 4 * 700 + i % 4 * 3).collect();
+        let pages: Vec<Vec<TxRecord>> = ledgers
+            .chunks(200)
+            .rev()
+            .map(|chunk| txs(chunk.iter().copied()))
+            .collect();
+
+        let (frequency, pulled) = analyze(pages.clone(), &bounds(Some(1000))).await;
+        assert!(matches!(frequency.truncated, Some(Truncation::Settled)));
+        assert_eq!(frequency.tx_count, 600);
+        assert_eq!(pulled, 3);
+
+        // Without a limit, any number of older transactions may come
+        let (frequency, pulled) = analyze(pages, &bounds(None)).await;
+        assert!(frequency.truncated.is_none());
+        assert_eq!(pulled, 5);
+    }
+
+    #[tokio::test]
+    async fn page_errors_are_returned() {
+        let pages = stream::iter([Ok(txs(10..20)), Err(Error::AnalysisAborted)]);
+        let result = analyze_pages(
+            pages,
+            ACCOUNT,
+            &bounds(None),
+            TimeDelta::seconds(60),
+            start(),
+        )
+        .await;
+        assert!(matches!(result, Err(Error::AnalysisAborted)));
+    }
 }
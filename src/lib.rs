@@ -0,0 +1,54 @@
+//! Admin Wallet Type Checker
+//!
+//! Analyzes Stellar accounts and contracts to determine their type:
+//! - For contracts: Follows the admin chain through nested admin contracts
+//! - For EOAs: Determines if it's a hot wallet, MPC, or multisig account
+//!
+//! The analysis is started from a [`Runner`], built with [`Runner::builder`]:
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), admin_checker::Error> {
+//! let runner = admin_checker::Runner::builder("CA...")
+//!     .network("mainnet")
+//!     .keys(["admin", "owner"])
+//!     .build()?;
+//! let report = runner.analyze().await?;
+//! println!("{}", report);
+//! # Ok(())
+//! # }
+//! ```
+
+mod account_type;
+mod admin_chain;
+mod discovery;
+mod duration;
+mod error;
+mod history;
+mod horizon_helper;
+mod network_config;
+mod report;
+mod role_hints;
+mod rpc_helper;
+mod runner;
+mod signers;
+mod storage_helper;
+#[cfg(test)]
+mod test_fixtures;
+mod ttl;
+mod tx_pattern;
+
+pub use account_type::{AccountType, Confidence, MultisigLevels};
+pub use admin_chain::{AdminChain, AdminLink, AdminMatch, ChainEnd};
+pub use discovery::Candidate;
+pub use duration::Duration;
+pub use error::Error;
+pub use history::{Since, Truncation, DEFAULT_MAX_TXS};
+pub use report::{AccountReport, Report, RoleFailure, RoleReport};
+pub use runner::{Runner, RunnerBuilder};
+pub use signers::{
+    check_if_centralized, AccountData, Signer, SignerCombination, SignerType, SpecialSigner,
+    ThresholdBreakdown, ThresholdLevel, Thresholds,
+};
+pub use storage_helper::{decode_admin_value, AddressType, DecodedAdmin, Durability, ValueAddress};
+pub use ttl::TtlStatus;
+pub use tx_pattern::TxFrequency;
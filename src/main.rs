@@ -1,22 +1,10 @@
-//! Admin Wallet Type Checker
-//!
-//! Analyzes Stellar accounts and contracts to determine their type:
-//! - For contracts: Identifies if it's a contract account
-//! - For EOAs: Determines if it's a hot wallet, MPC, or multisig account
+//! Command line interface of the admin wallet type checker
 
-mod account_type;
+mod batch;
 mod config;
-mod error;
-mod horizon_helper;
-mod network_config;
-mod runner;
-mod storage_helper;
-use account_type::AccountType;
+use admin_checker::Error;
 use clap::CommandFactory;
-use config::Config;
-use error::Error;
-use storage_helper::AddressType;
-use tokio;
+use config::{Config, OutputFormat};
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
@@ -26,13 +14,39 @@ async fn main() -> Result<(), Error> {
     }
 
     let config = Config::parce_args()?;
-    let runner = config.to_runner()?;
 
-    let account_type = match runner.find_key().await? {
-        AddressType::EOA(addr) => runner.is_hot_wallet(addr).await?,
-        AddressType::Contract => AccountType::Contract,
-    };
+    if let Some(input) = config.input() {
+        let targets = batch::read_targets(input)?;
+        let entries = batch::run(&config, targets).await;
 
-    println!("Account type: {}", account_type);
+        match config.format() {
+            OutputFormat::Text => println!("{}", batch::to_table(&entries)),
+            OutputFormat::Json => println!("{}", to_json(&entries)?),
+            OutputFormat::Csv => println!("{}", batch::to_csv(&entries)),
+        }
+        return Ok(());
+    }
+
+    let runner = config.to_runner()?;
+    let report = runner.analyze().await?;
+
+    match config.format() {
+        OutputFormat::Text => println!("{}", report),
+        OutputFormat::Json => println!("{}", to_json(&report)?),
+        OutputFormat::Csv => {
+            let target = batch::BatchTarget {
+                label: None,
+                address: report.target.clone(),
+            };
+            println!(
+                "{}",
+                batch::to_csv(&[batch::BatchEntry::new(target, Ok(report))])
+            )
+        }
+    }
     Ok(())
 }
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<String, Error> {
+    serde_json::to_string_pretty(value).map_err(|_| Error::ReportSerializationFailure)
+}
@@ -2,28 +2,30 @@ use crate::Error;
 use std::str::FromStr;
 use std::{fs, path::PathBuf};
 
+/// Endpoints of a network
 #[derive(Debug)]
-pub enum UrlType {
-    Rpc,
-    Horizon,
+pub struct NetworkUrls {
+    pub rpc: String,
+    /// Only well-known public networks have a Horizon URL
+    pub horizon: Option<String>,
 }
 
-/// Retrieves the appropriate URL for a given network and URL type.
+/// Retrieves the RPC and Horizon URLs of a network.
 ///
 /// # Arguments
 /// * `network` - The network name (e.g., "mainnet", "testnet")
-/// * `url_type` - The type of URL to retrieve
-pub fn url_for_network(network: &str, url_type: UrlType) -> Result<String, Error> {
-    match url_type {
-        UrlType::Rpc => {
-            if let Ok(rpc_url) = rpc_url_from_network(&network) {
-                Ok(rpc_url)
-            } else {
-                // If it's not a "well-known" network, try to load it from local config file
-                load_from_config(&network)
-            }
-        }
-        UrlType::Horizon => horizon_url_from_network(network),
+pub fn network_urls(network: &str) -> Result<NetworkUrls, Error> {
+    match rpc_url_from_network(network) {
+        Ok(rpc) => Ok(NetworkUrls {
+            rpc,
+            horizon: horizon_url_from_network(network).ok(),
+        }),
+        // If it's not a "well-known" network, try to load it from local config file.
+        // Custom networks only have an RPC URL
+        Err(_) => Ok(NetworkUrls {
+            rpc: load_from_config(network)?,
+            horizon: None,
+        }),
     }
 }
 
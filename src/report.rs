@@ -0,0 +1,228 @@
+use core::fmt;
+use serde::Serialize;
+
+use crate::{
+    account_type::{AccountType, Confidence},
+    admin_chain::AdminChain,
+    discovery::Candidate,
+    signers::{Signer, SpecialSigner, ThresholdBreakdown, Thresholds},
+    storage_helper::{describe_key, key_format, AddressType},
+    tx_pattern::TxFrequency,
+};
+
+/// Details gathered while classifying an EOA admin.
+#[derive(Debug, Clone, Serialize)]
+pub struct AccountReport {
+    /// Signers of the account and their weights
+    pub signers: Vec<Signer>,
+    /// Thresholds of the account
+    pub thresholds: Thresholds,
+    /// Minimum signer combinations for each threshold level
+    pub threshold_breakdown: ThresholdBreakdown,
+    /// Pre-auth transaction, hash-x and signed payload signers
+    pub special_signers: Vec<SpecialSigner>,
+    /// Transaction pattern figures, only fetched for single-signer accounts
+    pub tx_frequency: Option<TxFrequency>,
+    /// Classification of the account
+    pub account_type: AccountType,
+    /// How sure the classification is
+    pub confidence: Confidence,
+    /// Facts the classification is based on
+    pub evidence: Vec<String>,
+}
+
+impl fmt::Display for AccountReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Signers:")?;
+        for signer in &self.signers {
+            writeln!(
+                f,
+                "  {} ({}) weight {}",
+                signer.key, signer.signer_type, signer.weight
+            )?;
+        }
+        writeln!(
+            f,
+            "Thresholds: low {}, medium {}, high {}",
+            self.thresholds.low_threshold,
+            self.thresholds.med_threshold,
+            self.thresholds.high_threshold
+        )?;
+        writeln!(
+            f,
+            "Minimum signers: low {}, medium {}, high {}",
+            self.threshold_breakdown.low,
+            self.threshold_breakdown.medium,
+            self.threshold_breakdown.high
+        )?;
+        for signer in &self.special_signers {
+            let levels: Vec<String> = signer
+                .levels_reached
+                .iter()
+                .map(|l| l.to_string())
+                .collect();
+            writeln!(
+                f,
+                "Warning: {} signer {} with weight {}{}: {}",
+                signer.signer_type,
+                signer.key,
+                signer.weight,
+                if levels.is_empty() {
+                    String::new()
+                } else {
+                    format!(" (alone reaches {})", levels.join(", "))
+                },
+                signer.note
+            )?;
+        }
+        if let Some(tx_frequency) = &self.tx_frequency {
+            write!(f, "{}", tx_frequency)?;
+        }
+        Ok(())
+    }
+}
+
+/// Resolution of a single privileged role.
+#[derive(Debug, Clone, Serialize)]
+pub struct RoleReport {
+    /// Storage key of the role, as given on the command line
+    pub role: String,
+    /// Admin lookups made, empty when the target is an EOA
+    pub admin_chain: AdminChain,
+    /// Address that ultimately holds the role
+    pub admin: AddressType,
+    /// Details about the holder account, if it is an EOA
+    pub account: Option<AccountReport>,
+    /// Final classification
+    pub account_type: AccountType,
+    /// How sure the final classification is
+    pub confidence: Confidence,
+    /// Facts the final classification is based on
+    pub evidence: Vec<String>,
+}
+
+impl fmt::Display for RoleReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.admin_chain.links.is_empty() {
+            writeln!(f, "Admin chain: {}", self.admin_chain)?;
+        }
+        for link in &self.admin_chain.links {
+            writeln!(
+                f,
+                "  {} found under {} '{}' in {} storage of {}",
+                link.admin,
+                key_format(&link.key),
+                describe_key(&link.key),
+                link.durability,
+                link.contract
+            )?;
+            // Only detail the value when it is more than a bare address
+            if link.addresses.iter().any(|a| a.path != "$") {
+                for a in &link.addresses {
+                    writeln!(f, "    {} = {}", a.path, a.address)?;
+                }
+            }
+            if let Some(ttl) = &link.ttl {
+                writeln!(f, "    admin entry: {}", ttl)?;
+            }
+            if let Some(ttl) = &link.instance_ttl {
+                writeln!(f, "    contract instance: {}", ttl)?;
+            }
+            for error in &link.lookup_errors {
+                writeln!(f, "    Warning: matches may be incomplete, {}", error)?;
+            }
+            if link.conflict {
+                writeln!(f, "    Warning: matching entries hold different admins:")?;
+            } else if !link.matches.is_empty() {
+                writeln!(f, "    matching entries agree:")?;
+            }
+            for m in &link.matches {
+                writeln!(
+                    f,
+                    "      {} '{}' in {} storage: {}{}",
+                    key_format(&m.key),
+                    describe_key(&m.key),
+                    m.durability,
+                    m.admin,
+                    match (&m.account_type, &m.classification_error) {
+                        (_, Some(e)) => format!(" (not classified: {})", e),
+                        (Some(t), None) => format!(" ({})", t),
+                        (None, None) => String::new(),
+                    }
+                )?;
+            }
+        }
+        if let Some(account) = &self.account {
+            write!(f, "{}", account)?;
+        }
+        writeln!(f, "Evidence:")?;
+        for item in &self.evidence {
+            writeln!(f, "  - {}", item)?;
+        }
+        write!(
+            f,
+            "Account type: {} (confidence: {})",
+            self.account_type, self.confidence
+        )
+    }
+}
+
+/// Role which could not be resolved.
+#[derive(Debug, Clone, Serialize)]
+pub struct RoleFailure {
+    pub role: String,
+    pub error: String,
+}
+
+/// Full result of the analysis of a single target.
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    /// Analyzed contract or account
+    pub target: String,
+    /// Network name, if the URLs were not given explicitly
+    pub network: Option<String>,
+    /// Resolved RPC URL
+    pub rpc_url: String,
+    /// Resolved Horizon URL, if any. Without it, transaction history is not analyzed
+    pub horizon_url: Option<String>,
+    /// Address-valued instance storage entries, best first. Only filled in discovery mode
+    pub candidates: Vec<Candidate>,
+    /// Resolved privileged roles
+    pub roles: Vec<RoleReport>,
+    /// Roles which could not be resolved
+    pub missing_roles: Vec<RoleFailure>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !self.candidates.is_empty() {
+            writeln!(f, "Address entries in instance storage:")?;
+            for c in &self.candidates {
+                writeln!(
+                    f,
+                    "  [{:>3}] {} -> {}",
+                    c.score,
+                    describe_key(&c.key),
+                    c.address
+                )?;
+            }
+        }
+
+        // A single role is printed as is, several roles get a header each
+        if let ([role], []) = (self.roles.as_slice(), self.missing_roles.as_slice()) {
+            return write!(f, "{}", role);
+        }
+
+        let mut sections: Vec<String> = self
+            .roles
+            .iter()
+            .map(|role| format!("== Role: {} ==\n{}", role.role, role))
+            .collect();
+        sections.extend(
+            self.missing_roles
+                .iter()
+                .map(|m| format!("== Role: {} ==\nNot resolved: {}", m.role, m.error)),
+        );
+        write!(f, "{}", sections.join("\n\n"))
+    }
+}
@@ -0,0 +1,110 @@
+/// Key name fragments hinting at a privileged address, with their score.
+const KEY_HINTS: &[(&str, u32)] = &[
+    ("admin", 100),
+    ("owner", 90),
+    ("governance", 80),
+    ("governor", 80),
+    ("manager", 70),
+    ("operator", 60),
+    ("controller", 60),
+    ("authority", 60),
+    ("guardian", 50),
+    ("upgrader", 50),
+    ("pauser", 40),
+    ("minter", 40),
+    ("council", 40),
+    ("dao", 30),
+];
+
+/// Words of a value path hinting at an address which does not control the contract yet
+const INACTIVE_HINTS: &[&str] = &["pending", "proposed", "candidate", "new", "next"];
+
+/// Scores a key name against the known privileged role names.
+///
+/// # Arguments
+/// * `name` - The key name
+pub fn key_score(name: &str) -> u32 {
+    let name = name.to_ascii_lowercase();
+    KEY_HINTS
+        .iter()
+        .filter(|(hint, _)| name.contains(hint))
+        .map(|(_, score)| *score)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Checks whether the path of an address inside a storage value points to an admin which is
+/// not in control yet, such as `$.pending_admin` or `$.newOwner`.
+///
+/// Whole words are compared, so `$.renewal_admin` is not taken as a new admin.
+///
+/// # Arguments
+/// * `path` - Path of the address inside the value
+pub fn is_inactive_path(path: &str) -> bool {
+    split_words(path)
+        .iter()
+        .any(|word| INACTIVE_HINTS.contains(&word.to_ascii_lowercase().as_str()))
+}
+
+/// Splits a key or value path into words on `_`, `-`, any other punctuation and camelCase boundaries.
+///
+/// Acronyms are kept together, so `superAdmin` and `XLMAdmin` give `super`, `Admin`
+/// and `XLM`, `Admin`.
+pub fn split_words(key: &str) -> Vec<String> {
+    let chars: Vec<char> = key.chars().collect();
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let next = chars.get(i + 1);
+        let boundary = c.is_ascii_uppercase()
+            && prev.is_some_and(|p| {
+                p.is_ascii_lowercase()
+                    || p.is_ascii_digit()
+                    || (p.is_ascii_uppercase() && next.is_some_and(|n| n.is_ascii_lowercase()))
+            });
+        if boundary && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+        }
+        current.push(c);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_inactive_hints_as_whole_words() {
+        for path in [
+            "$.pending_admin",
+            "$.newOwner",
+            "$.NextAdmin",
+            "$[1].candidate",
+            "$.new",
+        ] {
+            assert!(is_inactive_path(path), "{}", path);
+        }
+        for path in [
+            "$",
+            "$.renewal_admin",
+            "$.nextensionAdmin",
+            "$.admin[0]",
+            "$.renewedAt",
+        ] {
+            assert!(!is_inactive_path(path), "{}", path);
+        }
+    }
+}
@@ -0,0 +1,174 @@
+use std::{str::FromStr, sync::Arc};
+use stellar_rpc_client::{Client, GetLedgerEntriesResponse};
+use stellar_xdr::curr::{
+    AccountEntry, AccountId, LedgerEntryData, LedgerKey, LedgerKeyAccount, Limits, ReadXdr,
+    SignerKey,
+};
+
+use crate::{
+    error::Error,
+    signers::{AccountData, Signer, SignerType, Thresholds},
+};
+
+/// Fetches the signers and thresholds of a "G" account from its ledger entry.
+///
+/// Returns `Error::AccountNotFound` if the account has never been funded.
+///
+/// # Arguments
+/// * `rpc` - The RPC client
+/// * `rpc_url` - The RPC endpoint, used for error reporting
+/// * `account_id` - The Stellar account ID to fetch
+pub async fn get_account_data(
+    rpc: &Client,
+    rpc_url: &str,
+    account_id: &str,
+) -> Result<AccountData, Error> {
+    let key = LedgerKey::Account(LedgerKeyAccount {
+        account_id: AccountId::from_str(account_id).map_err(|_| Error::MalformedAddress)?,
+    });
+    let response =
+        rpc.get_ledger_entries(&[key])
+            .await
+            .map_err(|e| Error::AccountFetchFailure {
+                url: rpc_url.to_string(),
+                source: Arc::new(e),
+            })?;
+
+    decode_account_entry(&response, account_id)
+}
+
+/// Decodes the account entry of a `getLedgerEntries` response.
+///
+/// # Arguments
+/// * `response` - The RPC response
+/// * `account_id` - The account the entry was looked up for
+pub fn decode_account_entry(
+    response: &GetLedgerEntriesResponse,
+    account_id: &str,
+) -> Result<AccountData, Error> {
+    let entry = response
+        .entries
+        .iter()
+        .flatten()
+        .next()
+        .ok_or_else(|| Error::AccountNotFound(account_id.to_string()))?;
+
+    match LedgerEntryData::from_xdr_base64(&entry.xdr, Limits::none())
+        .map_err(|e| Error::MalformedRpcResponse(e.to_string()))?
+    {
+        LedgerEntryData::Account(account) => Ok(account_data(&account)),
+        _ => Err(Error::MalformedRpcResponse(format!(
+            "expected account entry for {}",
+            account_id
+        ))),
+    }
+}
+
+/// Converts an account entry into the signers and thresholds Horizon would report.
+///
+/// The master key is listed first, as a regular ed25519 signer with the master weight.
+fn account_data(account: &AccountEntry) -> AccountData {
+    let [master_weight, low_threshold, med_threshold, high_threshold] = account.thresholds.0;
+
+    let mut signers = vec![Signer {
+        key: account.account_id.to_string(),
+        signer_type: SignerType::Ed25519PublicKey,
+        weight: master_weight,
+    }];
+    signers.extend(account.signers.iter().map(|s| Signer {
+        key: s.key.to_string(),
+        signer_type: match s.key {
+            SignerKey::Ed25519(_) => SignerType::Ed25519PublicKey,
+            SignerKey::PreAuthTx(_) => SignerType::PreauthTx,
+            SignerKey::HashX(_) => SignerType::Sha256Hash,
+            SignerKey::Ed25519SignedPayload(_) => SignerType::Ed25519SignedPayload,
+        },
+        // Signer weights are capped at 255 by the protocol
+        weight: s.weight.min(u8::MAX as u32) as u8,
+    }));
+
+    AccountData {
+        thresholds: Thresholds {
+            low_threshold,
+            med_threshold,
+            high_threshold,
+        },
+        signers,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{ledger_entries, ttl_entry};
+    use stellar_xdr::curr::{
+        AccountEntryExt, PublicKey, SequenceNumber, Signer as XdrSigner, String32,
+        Thresholds as XdrThresholds, Uint256, WriteXdr,
+    };
+
+    const ACCOUNT: &str = "GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7";
+
+    #[test]
+    fn missing_account_is_not_found() {
+        assert!(matches!(
+            decode_account_entry(&ledger_entries(&[]), ACCOUNT),
+            Err(Error::AccountNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn unexpected_entry_type_is_an_error() {
+        assert!(matches!(
+            decode_account_entry(&ledger_entries(&[&ttl_entry()]), ACCOUNT),
+            Err(Error::MalformedRpcResponse(_))
+        ));
+        assert!(matches!(
+            decode_account_entry(&ledger_entries(&["AAAA"]), ACCOUNT),
+            Err(Error::MalformedRpcResponse(_))
+        ));
+    }
+
+    #[test]
+    fn decodes_signers_and_thresholds() {
+        let account = LedgerEntryData::Account(AccountEntry {
+            account_id: AccountId::from_str(ACCOUNT).unwrap(),
+            balance: 10_000_000,
+            seq_num: SequenceNumber(1),
+            num_sub_entries: 2,
+            inflation_dest: None,
+            flags: 0,
+            home_domain: String32::default(),
+            thresholds: XdrThresholds([0, 1, 2, 3]),
+            signers: vec![
+                XdrSigner {
+                    key: SignerKey::Ed25519(Uint256([1; 32])),
+                    weight: 2,
+                },
+                XdrSigner {
+                    key: SignerKey::HashX(Uint256([2; 32])),
+                    weight: 1,
+                },
+            ]
+            .try_into()
+            .unwrap(),
+            ext: AccountEntryExt::V0,
+        })
+        .to_xdr_base64(Limits::none())
+        .unwrap();
+
+        let data = decode_account_entry(&ledger_entries(&[&account]), ACCOUNT).unwrap();
+        assert_eq!(data.thresholds.low_threshold, 1);
+        assert_eq!(data.thresholds.med_threshold, 2);
+        assert_eq!(data.thresholds.high_threshold, 3);
+        assert_eq!(data.signers.len(), 3);
+        assert_eq!(data.signers[0].key, ACCOUNT);
+        assert_eq!(data.signers[0].weight, 0);
+        assert_eq!(
+            data.signers[1].key,
+            PublicKey::PublicKeyTypeEd25519(Uint256([1; 32])).to_string()
+        );
+        assert_eq!(data.signers[1].signer_type, SignerType::Ed25519PublicKey);
+        assert_eq!(data.signers[2].signer_type, SignerType::Sha256Hash);
+        assert!(data.signers[2].key.starts_with('X'));
+    }
+}
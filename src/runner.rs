@@ -1,64 +1,545 @@
-use crate::{account_type::AccountType, error::Error, horizon_helper, storage_helper};
-use std::{collections::HashSet, str::FromStr};
+use crate::{
+    account_type::{AccountType, Confidence},
+    admin_chain::{AdminChain, AdminLink, AdminMatch, ChainEnd},
+    discovery,
+    duration::Duration,
+    error::Error,
+    history::{HistoryBounds, Since},
+    horizon_helper,
+    network_config::network_urls,
+    report::{AccountReport, Report, RoleFailure, RoleReport},
+    role_hints::split_words,
+    rpc_helper,
+    signers::{self, ThresholdLevel},
+    tx_pattern::DEFAULT_BACK_TO_BACK_SECS,
+};
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    str::FromStr,
+    sync::Arc,
+};
 use stellar_rpc_client::Client;
 use stellar_xdr::curr::{
-    ContractDataDurability, LedgerEntryData, LedgerKey, LedgerKeyContractData, Limits, ReadXdr,
-    ScAddress, ScMapEntry, ScVal,
+    ContractDataDurability, LedgerKey, LedgerKeyContractData, ScAddress, ScVal,
+};
+
+use crate::storage_helper::{
+    decode_admin_value, decode_instance_storage, decode_storage_entries, parse_key_expression,
+    parse_raw_key, possible_keys, wrap_eoa, AddressType, Durability, InstanceStorage, StorageEntry,
 };
 
-use crate::storage_helper::{decode_admin_value, possible_keys};
+/// Storage key the admin of nested admin contracts is looked up under
+const ADMIN_KEY: &str = "admin";
+
+/// A privileged role and the storage keys it may be stored under
+struct Role {
+    name: String,
+    keys: Vec<ScVal>,
+}
+
+impl Role {
+    /// Whether this is the `admin` role, the only one a discovered candidate may stand in for
+    fn is_admin(&self) -> bool {
+        self.name.eq_ignore_ascii_case(ADMIN_KEY)
+    }
+}
 
 /// Runner handles the core logic
 pub struct Runner {
     rpc: Client,
-    horizon: String,
+    rpc_url: String,
+    network: Option<String>,
+    horizon: Option<String>,
     contract_id: ScAddress,
-    keys: HashSet<ScVal>,
+    roles: Vec<Role>,
+    /// Storage keys nested admin contracts are searched for
+    admin_keys: Vec<ScVal>,
+    max_depth: usize,
+    threshold_level: ThresholdLevel,
+    discover: bool,
+    history: HistoryBounds,
+    back_to_back: Duration,
+}
+
+/// Builds a [`Runner`] for a single target.
+///
+/// The RPC and Horizon URLs are inferred from the network when not given.
+/// Horizon is optional, without it the transaction history of EOA admins is not analyzed.
+/// Without any key, the `admin` role is resolved.
+#[derive(Debug, Clone)]
+pub struct RunnerBuilder {
+    contract_id: String,
+    rpc_url: Option<String>,
+    network: Option<String>,
+    horizon: Option<String>,
+    keys: Vec<String>,
+    raw_keys: Vec<String>,
+    max_depth: usize,
+    threshold_level: ThresholdLevel,
+    discover: bool,
+    history: HistoryBounds,
+    back_to_back: Duration,
+}
+
+impl RunnerBuilder {
+    /// Sets the RPC endpoint to use
+    pub fn rpc_url(mut self, rpc_url: impl Into<String>) -> Self {
+        self.rpc_url = Some(rpc_url.into());
+        self
+    }
+
+    /// Sets the network name, used to infer the URLs which are not given
+    pub fn network(mut self, network: impl Into<String>) -> Self {
+        self.network = Some(network.into());
+        self
+    }
+
+    /// Sets the Horizon URL to use for the transaction history of EOA admins
+    pub fn horizon_url(mut self, horizon: impl Into<String>) -> Self {
+        self.horizon = Some(horizon.into());
+        self
+    }
+
+    /// Sets the storage keys of the privileged roles to resolve. Defaults to `admin` when no raw
+    /// key is given. Enum variants with arguments are written as `Role:GABC...`
+    pub fn keys<I, S>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.keys = keys.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets additional storage keys given as base64 XDR or JSON, looked up as is
+    pub fn raw_keys<I, S>(mut self, raw_keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.raw_keys = raw_keys.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Sets how many nested admin contracts to follow. Defaults to 5
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Sets the threshold level EOA admins are classified against. Defaults to medium
+    pub fn threshold_level(mut self, threshold_level: ThresholdLevel) -> Self {
+        self.threshold_level = threshold_level;
+        self
+    }
+
+    /// Ranks every address in the instance storage, the best one standing in for the `admin` role
+    pub fn discover(mut self, discover: bool) -> Self {
+        self.discover = discover;
+        self
+    }
+
+    /// Sets the maximum number of most recent transactions to analyze, `None` for no limit.
+    /// Defaults to 1000
+    pub fn max_txs(mut self, max_txs: Option<usize>) -> Self {
+        self.history.max_txs = max_txs;
+        self
+    }
+
+    /// Only analyzes transactions from the given ledger or time on
+    pub fn since(mut self, since: Since) -> Self {
+        self.history.since = Some(since);
+        self
+    }
+
+    /// Only analyzes transactions within the given length of time before now
+    pub fn window(mut self, window: Duration) -> Self {
+        self.history.window = Some(window);
+        self
+    }
+
+    /// Sets the gap up to which consecutive transactions are back-to-back. Defaults to 1 minute
+    pub fn back_to_back(mut self, cutoff: Duration) -> Self {
+        self.back_to_back = cutoff;
+        self
+    }
+
+    /// Validates the configuration and creates the Runner.
+    pub fn build(self) -> Result<Runner, Error> {
+        // The network is only needed for the URLs which are not given
+        let network_urls = match (&self.network, &self.rpc_url, &self.horizon) {
+            (Some(network), None, _) | (Some(network), _, None) => Some(network_urls(network)?),
+            _ => None,
+        };
+        let (network_rpc, network_horizon) = match network_urls {
+            Some(urls) => (Some(urls.rpc), urls.horizon),
+            None => (None, None),
+        };
+        let rpc_url = self.rpc_url.or(network_rpc).ok_or(Error::MissingNetwork)?;
+        // Without Horizon, EOA admins are analyzed through RPC only
+        let horizon = self.horizon.or(network_horizon).map(|mut url| {
+            if !url.ends_with('/') {
+                url.push('/');
+            }
+            url
+        });
+
+        let keys = if self.keys.is_empty() && self.raw_keys.is_empty() {
+            vec![ADMIN_KEY.to_string()]
+        } else {
+            self.keys
+        };
+        let mut roles = keys
+            .iter()
+            .map(|key| {
+                let (name, args) = parse_key_expression(key)?;
+                Ok(Role {
+                    name: key.clone(),
+                    keys: possible_keys(mutate_input(&name), &args),
+                })
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        for raw in &self.raw_keys {
+            roles.push(Role {
+                name: raw.clone(),
+                keys: vec![parse_raw_key(raw)?],
+            });
+        }
+
+        Ok(Runner {
+            rpc: Client::new(&rpc_url).map_err(|e| Error::MalformedUrl {
+                url: rpc_url.clone(),
+                source: Arc::new(e),
+            })?,
+            rpc_url,
+            network: self.network,
+            horizon,
+            contract_id: ScAddress::from_str(&self.contract_id)
+                .map_err(|_| Error::MalformedAddress)?,
+            roles,
+            admin_keys: possible_keys(mutate_input(ADMIN_KEY), &[]),
+            max_depth: self.max_depth,
+            threshold_level: self.threshold_level,
+            discover: self.discover,
+            history: self.history,
+            back_to_back: self.back_to_back,
+        })
+    }
 }
 
 impl Runner {
-    /// Creates a new Runner instance with the specified configuration.
+    /// Starts building a Runner for the given target.
     ///
     /// # Arguments
-    /// * `rpc_url` - The RPC endpoint to use
-    /// * `horizon` - The Horizon URL to use
-    /// * `contract_id` - The ID of the contract to analyze
-    /// * `key` - The admin address' storage key
-    pub fn new(
-        rpc_url: &str,
-        horizon: String,
-        contract_id: &str,
-        key: &str,
-    ) -> Result<Self, Error> {
-        Ok(Self {
-            rpc: Client::new(rpc_url).map_err(|_| Error::MalformedUrl)?,
-            horizon,
-            contract_id: ScAddress::from_str(&contract_id).map_err(|_| Error::MalformedAddress)?,
-            keys: possible_keys(mutate_input(key)),
+    /// * `contract_id` - The contract ID or G-address to analyze
+    pub fn builder(contract_id: impl Into<String>) -> RunnerBuilder {
+        RunnerBuilder {
+            contract_id: contract_id.into(),
+            rpc_url: None,
+            network: None,
+            horizon: None,
+            keys: vec![],
+            raw_keys: vec![],
+            max_depth: 5,
+            threshold_level: ThresholdLevel::Medium,
+            discover: false,
+            history: HistoryBounds::default(),
+            back_to_back: Duration::seconds(DEFAULT_BACK_TO_BACK_SECS),
+        }
+    }
+
+    /// Runs the whole analysis for the target.
+    ///
+    /// Resolves every role independently. Roles which cannot be resolved are recorded
+    /// in the report, unless none of them can, in which case the first error is returned.
+    /// In discovery mode, the best ranked candidate is used as the `admin` role's holder when its
+    /// key name is admin-like enough, otherwise the role is looked up by key as usual. Other roles
+    /// are always looked up by key.
+    pub async fn analyze(&self) -> Result<Report, Error> {
+        let (candidates, instance_ttl) = match &self.contract_id {
+            ScAddress::Contract(_) if self.discover => {
+                // Persistent storage cannot be enumerated through RPC, so only the instance storage is scanned
+                let instance = self.get_contract_instance(&self.contract_id).await?;
+                (discovery::rank_candidates(&instance.storage), instance.ttl)
+            }
+            _ => (vec![], None),
+        };
+
+        let mut roles = Vec::new();
+        let mut missing_roles = Vec::new();
+        let mut first_error = None;
+        for role in &self.roles {
+            let selected = discovery::select_candidate(&candidates).filter(|_| role.is_admin());
+            let discovered = selected.map(|best| AdminLink {
+                contract: self.contract_id.to_string(),
+                key: best.key.clone(),
+                durability: Durability::Instance,
+                ttl: instance_ttl.clone(),
+                instance_ttl: instance_ttl.clone(),
+                admin: best.address.clone(),
+                addresses: best.addresses.clone(),
+                matches: vec![],
+                conflict: false,
+                lookup_errors: vec![],
+            });
+
+            match self.analyze_role(role, discovered).await {
+                Ok(report) => roles.push(report),
+                Err(e) => {
+                    missing_roles.push(RoleFailure {
+                        role: role.name.clone(),
+                        error: e.to_string(),
+                    });
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+
+        if roles.is_empty() {
+            return Err(first_error.unwrap_or(Error::AdminNotFound));
+        }
+
+        Ok(Report {
+            target: self.contract_id.to_string(),
+            network: self.network.clone(),
+            rpc_url: self.rpc_url.clone(),
+            horizon_url: self.horizon.clone(),
+            candidates,
+            roles,
+            missing_roles,
+        })
+    }
+
+    /// Follows the admin chain of a single role and, if it ends at an EOA, classifies that account.
+    ///
+    /// # Arguments
+    /// * `role` - The role to resolve
+    /// * `first` - The role's holder in the target, if already known
+    async fn analyze_role(
+        &self,
+        role: &Role,
+        first: Option<AdminLink>,
+    ) -> Result<RoleReport, Error> {
+        let mut admin_chain = match first {
+            Some(link) => self.follow_chain(&role.keys, Some(link)).await?,
+            None => self.find_key(&role.keys).await?,
+        };
+        let admin = match (admin_chain.admin(), &self.contract_id) {
+            (Some(admin), _) => admin.clone(),
+            (None, ScAddress::Account(id)) => wrap_eoa(id.clone()),
+            (None, ScAddress::Contract(_)) => return Err(Error::AdminNotFound),
+        };
+
+        let (account, account_type) = self.classify(&admin).await?;
+        let (mut confidence, mut evidence) = match (&account, &account_type) {
+            (Some(account), _) => (account.confidence, account.evidence.clone()),
+            (None, AccountType::Unfunded) => (
+                Confidence::High,
+                vec!["account does not exist on-chain".to_string()],
+            ),
+            (None, _) => {
+                // When the walk stops early, the contract found is not known to be the real controller
+                let confidence = match admin_chain.end {
+                    ChainEnd::Eoa | ChainEnd::NoAdmin | ChainEnd::MissingContract => {
+                        Confidence::High
+                    }
+                    ChainEnd::MaxDepth | ChainEnd::Cycle | ChainEnd::LookupFailed(_) => {
+                        Confidence::Low
+                    }
+                };
+                (
+                    confidence,
+                    vec![format!("admin chain {}", admin_chain.end.description())],
+                )
+            }
+        };
+        if admin_chain.links.iter().any(|l| l.conflict) {
+            confidence = confidence.min(Confidence::Medium);
+            evidence.push(
+                "matching entries hold different admins, the first one was followed".to_string(),
+            );
+        }
+
+        // Conflicting entries are classified one by one, so the riskiest holder stands out
+        let mut classified = HashMap::from([(admin.clone(), Ok(account_type.clone()))]);
+        for link in admin_chain.links.iter_mut().filter(|l| l.conflict) {
+            for m in link.matches.iter_mut() {
+                if !classified.contains_key(&m.admin) {
+                    let result = self
+                        .classify(&m.admin)
+                        .await
+                        .map(|(_, account_type)| account_type)
+                        .map_err(|e| e.to_string());
+                    classified.insert(m.admin.clone(), result);
+                }
+                match &classified[&m.admin] {
+                    Ok(account_type) => m.account_type = Some(account_type.clone()),
+                    Err(e) => {
+                        m.account_type = Some(AccountType::Inconclusive);
+                        m.classification_error = Some(e.clone());
+                    }
+                }
+            }
+        }
+
+        Ok(RoleReport {
+            role: role.name.clone(),
+            admin_chain,
+            admin,
+            account,
+            account_type,
+            confidence,
+            evidence,
         })
     }
 
-    /// Finds the admin key in contract storage or returns the EOA address.
+    /// Classifies an admin address, returning the account details if it is an existing EOA.
     ///
-    /// For EOAs, returns the address directly. For contracts, searches both
-    /// instance and persistent storage for the admin key.
-    pub async fn find_key(&self) -> Result<storage_helper::AddressType, Error> {
-        if let ScAddress::Account(id) = self.contract_id.clone() {
-            return Ok(storage_helper::wrap_eoa(id));
+    /// An EOA which does not exist on-chain is reported as unfunded instead of failing.
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address to classify
+    async fn classify(
+        &self,
+        admin: &AddressType,
+    ) -> Result<(Option<AccountReport>, AccountType), Error> {
+        match admin {
+            AddressType::EOA(addr) => match self.is_hot_wallet(addr.clone()).await {
+                Ok(account) => {
+                    let account_type = account.account_type.clone();
+                    Ok((Some(account), account_type))
+                }
+                Err(Error::AccountNotFound(_)) => Ok((None, AccountType::Unfunded)),
+                Err(e) => Err(e),
+            },
+            AddressType::Contract(_) => Ok((None, AccountType::Contract)),
+        }
+    }
+
+    /// Finds the admin key in contract storage.
+    ///
+    /// For EOAs, returns an empty chain. For contracts, searches both
+    /// instance and persistent storage for the admin key. If the admin is itself
+    /// a contract, its own admin is looked up as well under the `admin` key, up to
+    /// `max_depth` levels.
+    ///
+    /// # Arguments
+    /// * `keys` - The storage keys the admin may be stored under
+    async fn find_key(&self, keys: &[ScVal]) -> Result<AdminChain, Error> {
+        if let ScAddress::Account(_) = self.contract_id {
+            return Ok(AdminChain {
+                links: vec![],
+                end: ChainEnd::Eoa,
+            });
         }
 
-        let instance_storage = self.get_contract_instance().await?;
+        self.follow_chain(keys, None).await
+    }
 
-        let admin_val = if let Some(entry) = instance_storage
+    /// Walks the admin chain starting at the target contract.
+    ///
+    /// Only the target is searched for the role's keys, nested admin contracts are
+    /// searched for their own admin.
+    ///
+    /// # Arguments
+    /// * `keys` - The storage keys the role may be stored under in the target
+    /// * `first` - The target's admin, if already known
+    async fn follow_chain(
+        &self,
+        keys: &[ScVal],
+        first: Option<AdminLink>,
+    ) -> Result<AdminChain, Error> {
+        walk_chain(
+            self.contract_id.clone(),
+            first,
+            self.max_depth,
+            |contract, nested| {
+                let keys = if nested { &self.admin_keys[..] } else { keys };
+                async move { self.find_admin_of(&contract, keys).await }
+            },
+        )
+        .await
+    }
+
+    /// Looks up the admin of a single contract in its instance, persistent and temporary storage.
+    ///
+    /// # Arguments
+    /// * `contract_id` - The contract to look into
+    /// * `keys` - The storage keys the admin may be stored under
+    async fn find_admin_of(
+        &self,
+        contract_id: &ScAddress,
+        keys: &[ScVal],
+    ) -> Result<AdminLink, Error> {
+        let instance = self.get_contract_instance(contract_id).await?;
+
+        // Contracts may legitimately store several variants such as `Admin` and `admin`,
+        // so every matching entry is collected instead of the first one
+        let mut entries: Vec<StorageEntry> = instance
+            .storage
             .iter()
-            .find(|entry| self.keys.contains(&entry.key))
-        {
-            entry.val.clone()
-        } else {
-            self.persistent_storage_lookup().await?
+            .filter(|entry| keys.contains(&entry.key))
+            .map(|entry| StorageEntry {
+                key: entry.key.clone(),
+                val: entry.val.clone(),
+                durability: Durability::Instance,
+                ttl: instance.ttl.clone(),
+            })
+            .collect();
+        // A failed lookup leaves the matches incomplete, but those found are still worth reporting
+        let mut lookup_errors = Vec::new();
+        for durability in [Durability::Persistent, Durability::Temporary] {
+            match self.storage_lookup(contract_id, keys, durability).await {
+                Ok(found) => entries.extend(found),
+                Err(e) => lookup_errors.push(e),
+            }
+        }
+
+        // Entries of persistent and temporary storage come in no particular order
+        sort_entries(&mut entries, keys);
+
+        let mut decode_error = None;
+        let mut matches = Vec::new();
+        for entry in entries {
+            match decode_admin_value(&entry.val) {
+                Ok(decoded) => matches.push(AdminMatch {
+                    key: entry.key,
+                    durability: entry.durability,
+                    ttl: entry.ttl,
+                    admin: decoded.admin,
+                    addresses: decoded.addresses,
+                    account_type: None,
+                    classification_error: None,
+                }),
+                Err(e) => decode_error = decode_error.or(Some(e)),
+            }
+        }
+
+        let Some(first) = matches.first().cloned() else {
+            return Err(lookup_errors
+                .into_iter()
+                .next()
+                .or(decode_error)
+                .unwrap_or(Error::AdminNotFound));
         };
+        let conflict = matches.iter().any(|m| m.admin != first.admin);
+        if matches.len() == 1 {
+            matches.clear();
+        }
 
-        decode_admin_value(&admin_val)
+        Ok(AdminLink {
+            contract: contract_id.to_string(),
+            admin: first.admin,
+            addresses: first.addresses,
+            key: first.key,
+            durability: first.durability,
+            ttl: first.ttl,
+            instance_ttl: instance.ttl,
+            matches,
+            conflict,
+            lookup_errors: lookup_errors.iter().map(|e| e.to_string()).collect(),
+        })
     }
 
     /// Determines if an EOA is a hot wallet or MPC based on transaction patterns.
@@ -67,109 +548,494 @@ impl Runner {
     /// * `admin_address` - The address of the EOA to analyze
     ///
     /// Returns the account type based on:
-    /// - Signer weights and thresholds
-    /// - Transaction frequency patterns
-    pub async fn is_hot_wallet(&self, admin_address: String) -> Result<AccountType, Error> {
-        let account_type =
-            horizon_helper::check_if_centralized(self.horizon.clone(), admin_address.clone())
-                .await?;
-
-        match account_type {
-            AccountType::HotWallet => {
-                let min_ledger_diff_between_txs =
-                    horizon_helper::tx_frequency_for_account(self.horizon.clone(), admin_address)
-                        .await?;
-                // If there's less than 12 ledgers (1 min) between transactions, it's likely a hot wallet
-                if min_ledger_diff_between_txs <= 12 {
-                    Ok(AccountType::HotWallet)
-                } else {
-                    Ok(AccountType::MPC)
-                }
-            }
-            _ => Ok(account_type),
-        }
+    /// - Signer weights and thresholds, read from the account's ledger entry through RPC
+    /// - The distribution of the account's transactions over time, when a Horizon URL is available.
+    ///   Paging through the history stops as soon as older transactions cannot change the verdict
+    pub async fn is_hot_wallet(&self, admin_address: String) -> Result<AccountReport, Error> {
+        let data = rpc_helper::get_account_data(&self.rpc, &self.rpc_url, &admin_address).await?;
+
+        let mut evidence = signers::signer_evidence(&data, self.threshold_level);
+        let (account_type, confidence, tx_frequency) =
+            match signers::check_if_centralized(&data, self.threshold_level) {
+                AccountType::HotWallet => match &self.horizon {
+                    Some(horizon) => match horizon_helper::tx_frequency_for_account(
+                        horizon.clone(),
+                        admin_address,
+                        &self.history,
+                        self.back_to_back.0,
+                    )
+                    .await
+                    {
+                        Ok(tx_frequency) => {
+                            let (account_type, confidence) = tx_frequency.verdict();
+                            evidence.extend(tx_frequency.evidence());
+                            (account_type, confidence, Some(tx_frequency))
+                        }
+                        // The signer layout is known, only the history based verdict is missing
+                        Err(e) => {
+                            evidence.push(format!("transaction history not analyzed, {}", e));
+                            (AccountType::Inconclusive, Confidence::Low, None)
+                        }
+                    },
+                    // Without transaction history, a single signer is all there is to go by
+                    None => {
+                        evidence.push(
+                            "transaction history not analyzed, no Horizon URL available"
+                                .to_string(),
+                        );
+                        (AccountType::HotWallet, Confidence::Low, None)
+                    }
+                },
+                // Signer layouts are read from the ledger, there is nothing to guess
+                account_type => (account_type, Confidence::High, None),
+            };
+
+        Ok(AccountReport {
+            threshold_breakdown: signers::threshold_breakdown(&data),
+            special_signers: signers::special_signers(&data),
+            signers: data.signers,
+            thresholds: data.thresholds,
+            tx_frequency,
+            account_type,
+            confidence,
+            evidence,
+        })
     }
 
-    /// Looks up the admin key in persistent contract storage.
+    /// Looks up the admin key in persistent or temporary contract storage.
+    ///
+    /// Returns every entry stored under one of the keys, which is empty if there is none.
     ///
-    /// Used in case the admin key is not found in the instance storage.
-    async fn persistent_storage_lookup(&self) -> Result<ScVal, Error> {
-        let result = self
+    /// # Arguments
+    /// * `contract_id` - The contract to look into
+    /// * `keys` - The storage keys the admin may be stored under
+    /// * `durability` - Either `Durability::Persistent` or `Durability::Temporary`
+    async fn storage_lookup(
+        &self,
+        contract_id: &ScAddress,
+        keys: &[ScVal],
+        durability: Durability,
+    ) -> Result<Vec<StorageEntry>, Error> {
+        let response = self
             .rpc
-            .get_ledger_entries(&self.persistent_storage_keys())
-            .await;
+            .get_ledger_entries(&self.storage_keys(contract_id, keys, durability))
+            .await
+            .map_err(|e| {
+                let (url, source) = (self.rpc_url.clone(), Arc::new(e));
+                match durability {
+                    Durability::Temporary => Error::TemporaryStorageFailure { url, source },
+                    _ => Error::PersistentStorageFailure { url, source },
+                }
+            })?;
 
-        if let Ok(entries_) = result {
-            let entries = entries_.entries.unwrap();
+        decode_storage_entries(&response, durability)
+    }
 
-            match entries.len() {
-                0 => return Err(Error::AdminNotFound),
-                1 => (),
-                _ => return Err(Error::MultipleAdminsFound),
-            }
+    /// Retrieves the contract instance storage and its TTL.
+    async fn get_contract_instance(
+        &self,
+        contract_id: &ScAddress,
+    ) -> Result<InstanceStorage, Error> {
+        if let ScAddress::Account(_) = contract_id {
+            return Err(Error::NotAContract);
+        }
 
-            let entry = entries.get(0).unwrap();
-            let val = LedgerEntryData::from_xdr_base64(entry.xdr.clone(), Limits::none()).unwrap();
-            if let LedgerEntryData::ContractData(data) = val {
-                Ok(data.val)
-            } else {
-                Err(Error::AdminNotFound)
+        let key = LedgerKey::ContractData(LedgerKeyContractData {
+            contract: contract_id.clone(),
+            key: ScVal::LedgerKeyContractInstance,
+            durability: ContractDataDurability::Persistent,
+        });
+        let response = self.rpc.get_ledger_entries(&[key]).await.map_err(|e| {
+            Error::InstanceStorageFailure {
+                url: self.rpc_url.clone(),
+                source: Arc::new(e),
             }
-        } else {
-            Err(Error::PersistentStorageFailure)
-        }
-    }
-
-    /// Retrieves the contract instance storage.
-    async fn get_contract_instance(&self) -> Result<Vec<ScMapEntry>, Error> {
-        if let ScAddress::Contract(hash) = &self.contract_id {
-            let instance = self
-                .rpc
-                .get_contract_instance(&hash.0)
-                .await
-                .map_err(|_| Error::InstanceStorageFailure)?;
-            if let Some(storage) = instance.storage {
-                Ok(storage.0.to_vec())
-            } else {
-                Ok(vec![])
-            }
-        } else {
-            Err(Error::NotAContract)
-        }
+        })?;
+
+        decode_instance_storage(&response, &contract_id.to_string())
     }
 
-    /// Generates ledger keys for persistent storage lookup.
-    fn persistent_storage_keys(&self) -> Vec<LedgerKey> {
-        self.keys
-            .iter()
+    /// Generates ledger keys for persistent or temporary storage lookup.
+    fn storage_keys(
+        &self,
+        contract_id: &ScAddress,
+        keys: &[ScVal],
+        durability: Durability,
+    ) -> Vec<LedgerKey> {
+        let durability = match durability {
+            Durability::Temporary => ContractDataDurability::Temporary,
+            _ => ContractDataDurability::Persistent,
+        };
+        keys.iter()
             .map(|k| {
                 LedgerKey::ContractData(LedgerKeyContractData {
-                    contract: self.contract_id.clone(),
+                    contract: contract_id.clone(),
                     key: k.clone(),
-                    durability: ContractDataDurability::Persistent,
+                    durability,
                 })
             })
             .collect()
     }
 }
 
+/// Walks an admin chain, looking up the admin of each admin contract in turn.
+///
+/// Only a failure to look up the target's own admin is an error. Once the chain has
+/// started, a failed lookup stops the walk and the links resolved so far are kept.
+///
+/// # Arguments
+/// * `target` - The contract the chain starts at
+/// * `first` - The target's admin, if already known
+/// * `max_depth` - Maximum number of links
+/// * `lookup` - Looks up the admin of a contract, told whether it is a nested admin contract
+async fn walk_chain<F, Fut>(
+    target: ScAddress,
+    mut first: Option<AdminLink>,
+    max_depth: usize,
+    mut lookup: F,
+) -> Result<AdminChain, Error>
+where
+    F: FnMut(ScAddress, bool) -> Fut,
+    Fut: Future<Output = Result<AdminLink, Error>>,
+{
+    let mut visited = HashSet::from([target.clone()]);
+    let mut links: Vec<AdminLink> = Vec::new();
+    let mut current = target;
+
+    loop {
+        let lookup = match first.take() {
+            Some(link) => Ok(link),
+            None => lookup(current.clone(), !links.is_empty()).await,
+        };
+        let link = match lookup {
+            Ok(link) => link,
+            Err(e) if links.is_empty() => return Err(e),
+            Err(e) => {
+                let end = match e {
+                    // Admin contracts such as multisigs or governors usually have no admin themselves
+                    Error::AdminNotFound | Error::WrongStorageType => ChainEnd::NoAdmin,
+                    Error::ContractNotFound(_) => ChainEnd::MissingContract,
+                    e => ChainEnd::LookupFailed(e.to_string()),
+                };
+                return Ok(AdminChain { links, end });
+            }
+        };
+
+        let end = match &link.admin {
+            AddressType::EOA(_) => Some(ChainEnd::Eoa),
+            AddressType::Contract(addr) => {
+                current = ScAddress::from_str(addr).map_err(|_| Error::MalformedAddress)?;
+                if !visited.insert(current.clone()) {
+                    Some(ChainEnd::Cycle)
+                } else if links.len() + 1 >= max_depth {
+                    Some(ChainEnd::MaxDepth)
+                } else {
+                    None
+                }
+            }
+        };
+
+        links.push(link);
+        if let Some(end) = end {
+            return Ok(AdminChain { links, end });
+        }
+    }
+}
+
+/// Sorts storage entries by storage, then by the preference order of their key, so that
+/// the admin followed among conflicting entries does not change from one run to the next.
+///
+/// # Arguments
+/// * `entries` - The entries to sort
+/// * `keys` - The storage keys looked up, in order of preference
+fn sort_entries(entries: &mut [StorageEntry], keys: &[ScVal]) {
+    entries.sort_by_key(|entry| {
+        let rank = keys.iter().position(|key| *key == entry.key);
+        (entry.durability, rank)
+    });
+}
+
 /// Generates variations of the key for storage lookup.
+///
+/// Besides the key as given, its lowercase, UPPERCASE and Capitalized forms, multi-word
+/// keys are also converted to snake_case, camelCase, PascalCase and SCREAMING_SNAKE_CASE.
 fn mutate_input(key: &str) -> Vec<String> {
-    let capitalized = key
-        .chars()
-        .enumerate()
-        .map(|(i, c)| {
-            if i == 0 {
-                c.to_ascii_uppercase()
-            } else {
-                c.to_ascii_lowercase()
-            }
-        })
-        .collect();
+    let words = split_words(key);
 
-    vec![
+    let capitalize = |word: &str| -> String {
+        word.chars()
+            .enumerate()
+            .map(|(i, c)| {
+                if i == 0 {
+                    c.to_ascii_uppercase()
+                } else {
+                    c.to_ascii_lowercase()
+                }
+            })
+            .collect()
+    };
+    let pascal: String = words.iter().map(|w| capitalize(w)).collect();
+    let camel = match words.split_first() {
+        Some((first, rest)) => {
+            first.to_ascii_lowercase() + &rest.iter().map(|w| capitalize(w)).collect::<String>()
+        }
+        None => String::new(),
+    };
+    let snake = words.join("_").to_ascii_lowercase();
+
+    let mut variants = Vec::new();
+    for variant in [
+        key.to_string(),
         key.to_ascii_lowercase(),
         key.to_ascii_uppercase(),
-        capitalized,
-    ]
+        capitalize(key),
+        snake.clone(),
+        snake.to_ascii_uppercase(),
+        camel,
+        pascal,
+    ] {
+        if !variant.is_empty() && !variants.contains(&variant) {
+            variants.push(variant);
+        }
+    }
+    variants
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: &str = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC";
+    const ACCOUNT: &str = "GBXGQJWVLWOYHFLVTKWV5FGHA3LNYY2JQKM7OAJAUEQFU6LPCSEFVXON";
+
+    #[test]
+    fn splits_words_of_camel_and_snake_case_keys() {
+        assert_eq!(split_words("superAdmin"), ["super", "Admin"]);
+        assert_eq!(split_words("pending_admin"), ["pending", "admin"]);
+        assert_eq!(split_words("FEE-RECIPIENT"), ["FEE", "RECIPIENT"]);
+        assert_eq!(split_words("XLMAdmin"), ["XLM", "Admin"]);
+        assert_eq!(split_words("admin2Key"), ["admin2", "Key"]);
+        assert_eq!(split_words("admin"), ["admin"]);
+        assert!(split_words("__").is_empty());
+    }
+
+    #[test]
+    fn generates_case_variants() {
+        assert_eq!(
+            mutate_input("pending_admin"),
+            [
+                "pending_admin",
+                "PENDING_ADMIN",
+                "Pending_admin",
+                "pendingAdmin",
+                "PendingAdmin"
+            ]
+        );
+        assert_eq!(
+            mutate_input("superAdmin"),
+            [
+                "superAdmin",
+                "superadmin",
+                "SUPERADMIN",
+                "Superadmin",
+                "super_admin",
+                "SUPER_ADMIN",
+                "SuperAdmin"
+            ]
+        );
+        assert_eq!(mutate_input("admin"), ["admin", "ADMIN", "Admin"]);
+        // Acronyms stay one word
+        assert!(mutate_input("XLMAdmin").contains(&"XLM_ADMIN".to_string()));
+        assert!(mutate_input("XLMAdmin").contains(&"xlmAdmin".to_string()));
+    }
+
+    #[test]
+    fn symbol_keys_are_capped_at_the_symbol_length() {
+        // 32 characters, but 38 once converted to snake_case
+        let key = "adminAdminAdminAdminAdminAdminAd";
+        let variants = mutate_input(key);
+        assert!(variants.iter().any(|v| v.len() > 32));
+
+        let keys = possible_keys(variants.clone(), &[]);
+        for key in &keys {
+            if let ScVal::Symbol(s) = key {
+                assert!(s.len() <= 32);
+            }
+        }
+        // Every variant is still looked up as a string
+        let strings = keys.iter().filter(|k| matches!(k, ScVal::String(_)));
+        assert_eq!(strings.count(), variants.len());
+    }
+
+    fn discovered_roles(builder: RunnerBuilder) -> Vec<String> {
+        let runner = builder.rpc_url("http://localhost:8000").build().unwrap();
+        runner
+            .roles
+            .iter()
+            .filter(|role| role.is_admin())
+            .map(|role| role.name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn discovery_only_stands_in_for_the_admin_role() {
+        let builder = || Runner::builder(CONTRACT).discover(true);
+
+        assert_eq!(discovered_roles(builder()), ["admin"]);
+        assert_eq!(
+            discovered_roles(builder().keys(["owner", "Admin"])),
+            ["Admin"]
+        );
+        assert!(discovered_roles(builder().keys(["pauser"])).is_empty());
+        assert!(discovered_roles(builder().keys(["owner", "pauser"])).is_empty());
+        assert!(discovered_roles(builder().raw_keys([r#"{"symbol":"Owner"}"#])).is_empty());
+    }
+
+    #[test]
+    fn sorts_conflicting_entries_by_storage_then_key() {
+        let keys = possible_keys(mutate_input("admin"), &[]);
+        let key = |i: usize| keys[i].clone();
+        let entry = |key: ScVal, durability| StorageEntry {
+            key,
+            val: ScVal::Void,
+            durability,
+            ttl: None,
+        };
+        // admin (enum, symbol, string), then ADMIN, then Admin
+        assert_eq!(keys.len(), 9);
+
+        let mut entries = vec![
+            entry(key(8), Durability::Temporary),
+            entry(key(6), Durability::Persistent),
+            entry(key(1), Durability::Persistent),
+            entry(key(3), Durability::Persistent),
+            entry(key(7), Durability::Instance),
+        ];
+        sort_entries(&mut entries, &keys);
+
+        let order: Vec<_> = entries
+            .iter()
+            .map(|e| (e.durability, keys.iter().position(|k| *k == e.key).unwrap()))
+            .collect();
+        assert_eq!(
+            order,
+            [
+                (Durability::Instance, 7),
+                (Durability::Persistent, 1),
+                (Durability::Persistent, 3),
+                (Durability::Persistent, 6),
+                (Durability::Temporary, 8),
+            ]
+        );
+    }
+
+    fn contract(i: u8) -> ScAddress {
+        ScAddress::Contract(stellar_xdr::curr::Hash([i; 32]))
+    }
+
+    fn link(contract: &ScAddress, admin: AddressType) -> AdminLink {
+        AdminLink {
+            contract: contract.to_string(),
+            key: ScVal::Void,
+            durability: Durability::Instance,
+            ttl: None,
+            instance_ttl: None,
+            admin,
+            addresses: vec![],
+            matches: vec![],
+            conflict: false,
+            lookup_errors: vec![],
+        }
+    }
+
+    /// Walks a chain where contract `i` has the admin at `admins[i]`, contract 0 being the target
+    async fn walk(
+        admins: Vec<Result<AddressType, Error>>,
+        max_depth: usize,
+    ) -> Result<AdminChain, Error> {
+        walk_chain(contract(0), None, max_depth, |current, nested| {
+            let i = (0..admins.len())
+                .find(|i| contract(*i as u8) == current)
+                .expect("unknown contract");
+            assert_eq!(nested, i != 0);
+            let result = admins[i].clone().map(|admin| link(&current, admin));
+            std::future::ready(result)
+        })
+        .await
+    }
+
+    fn admin_contract(i: u8) -> AddressType {
+        AddressType::Contract(contract(i).to_string())
+    }
+
+    fn ends(chain: &AdminChain) -> String {
+        format!("{} {:?}", chain.links.len(), chain.end)
+    }
+
+    #[tokio::test]
+    async fn walks_nested_admin_contracts() {
+        let eoa = AddressType::EOA(ACCOUNT.to_string());
+        let chain = walk(vec![Ok(admin_contract(1)), Ok(eoa.clone())], 5).await;
+        assert_eq!(ends(&chain.unwrap()), "2 Eoa");
+
+        let chain = walk(vec![Ok(admin_contract(1)), Err(Error::AdminNotFound)], 5).await;
+        assert_eq!(ends(&chain.unwrap()), "1 NoAdmin");
+
+        let chain = walk(vec![Ok(admin_contract(1)), Ok(admin_contract(0))], 5).await;
+        assert_eq!(ends(&chain.unwrap()), "2 Cycle");
+
+        let admins = (1..=5).map(|i| Ok(admin_contract(i))).collect();
+        assert_eq!(ends(&walk(admins, 3).await.unwrap()), "3 MaxDepth");
+
+        assert!(matches!(
+            walk(vec![Err(Error::AdminNotFound)], 5).await,
+            Err(Error::AdminNotFound)
+        ));
+    }
+
+    #[tokio::test]
+    async fn keeps_the_chain_when_a_nested_lookup_fails() {
+        let missing = Error::ContractNotFound(contract(1).to_string());
+        let chain = walk(vec![Ok(admin_contract(1)), Err(missing)], 5).await;
+        assert_eq!(ends(&chain.unwrap()), "1 MissingContract");
+
+        let failure = Error::MalformedRpcResponse("no entries".to_string());
+        let chain = walk(vec![Ok(admin_contract(1)), Err(failure)], 5)
+            .await
+            .unwrap();
+        assert_eq!(chain.links.len(), 1);
+        assert!(matches!(chain.end, ChainEnd::LookupFailed(e) if e.contains("no entries")));
+    }
+
+    #[test]
+    fn infers_urls_from_the_network() {
+        let runner = Runner::builder(CONTRACT)
+            .network("testnet")
+            .build()
+            .unwrap();
+        assert_eq!(runner.rpc_url, "https://soroban-testnet.stellar.org");
+        assert_eq!(
+            runner.horizon.as_deref(),
+            Some("https://horizon-testnet.stellar.org/")
+        );
+
+        let runner = Runner::builder(CONTRACT)
+            .network("local")
+            .horizon_url("http://localhost:8000")
+            .build()
+            .unwrap();
+        assert_eq!(runner.rpc_url, "http://localhost:8000/soroban/rpc");
+        assert_eq!(runner.horizon.as_deref(), Some("http://localhost:8000/"));
+
+        let runner = Runner::builder(CONTRACT)
+            .rpc_url("http://localhost:8000")
+            .build()
+            .unwrap();
+        assert!(runner.horizon.is_none());
+
+        assert!(matches!(
+            Runner::builder(CONTRACT).build(),
+            Err(Error::MissingNetwork)
+        ));
+    }
 }
@@ -0,0 +1,421 @@
+use clap::ValueEnum;
+use core::fmt;
+use serde::Serialize;
+
+use crate::account_type::{AccountType, MultisigLevels};
+
+/// Threshold level an operation is checked against
+#[derive(ValueEnum, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ThresholdLevel {
+    /// Allow trust, bump sequence
+    Low,
+    /// Payments, contract invocations and everything else
+    Medium,
+    /// Set options (signers, thresholds) and account merge
+    High,
+}
+
+impl fmt::Display for ThresholdLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ThresholdLevel::Low => write!(f, "low"),
+            ThresholdLevel::Medium => write!(f, "medium"),
+            ThresholdLevel::High => write!(f, "high"),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Thresholds {
+    pub low_threshold: u8,
+    pub med_threshold: u8,
+    pub high_threshold: u8,
+}
+
+impl Thresholds {
+    /// Returns the threshold for the given level
+    pub fn get(&self, level: ThresholdLevel) -> u8 {
+        match level {
+            ThresholdLevel::Low => self.low_threshold,
+            ThresholdLevel::Medium => self.med_threshold,
+            ThresholdLevel::High => self.high_threshold,
+        }
+    }
+}
+
+/// Kind of key a signer represents, named as Horizon reports it
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerType {
+    Ed25519PublicKey,
+    PreauthTx,
+    Sha256Hash,
+    Ed25519SignedPayload,
+}
+
+impl fmt::Display for SignerType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SignerType::Ed25519PublicKey => write!(f, "ed25519"),
+            SignerType::PreauthTx => write!(f, "pre-auth tx"),
+            SignerType::Sha256Hash => write!(f, "hash-x"),
+            SignerType::Ed25519SignedPayload => write!(f, "signed payload"),
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct Signer {
+    pub key: String,
+    #[serde(rename = "type")]
+    pub signer_type: SignerType,
+    pub weight: u8,
+}
+
+/// Signer which is not a plain ed25519 key, with an explanation of its risk.
+#[derive(Serialize, Debug, Clone)]
+pub struct SpecialSigner {
+    pub key: String,
+    pub signer_type: SignerType,
+    pub weight: u8,
+    /// Threshold levels this signer reaches on its own
+    pub levels_reached: Vec<ThresholdLevel>,
+    pub note: String,
+}
+
+/// Smallest set of signers whose combined weight reaches a threshold.
+#[derive(Serialize, Debug, Clone)]
+pub struct SignerCombination {
+    pub threshold: u8,
+    /// Keys of the signers, `None` if the threshold cannot be reached
+    pub signers: Option<Vec<String>>,
+    /// Number of signers with a non-zero weight
+    pub total_signers: usize,
+}
+
+impl fmt::Display for SignerCombination {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.signers {
+            Some(signers) => write!(f, "{} of {}", signers.len(), self.total_signers),
+            None => write!(f, "unreachable"),
+        }
+    }
+}
+
+/// Minimum signer combinations for each threshold level.
+#[derive(Serialize, Debug, Clone)]
+pub struct ThresholdBreakdown {
+    pub low: SignerCombination,
+    pub medium: SignerCombination,
+    pub high: SignerCombination,
+}
+
+impl ThresholdBreakdown {
+    /// Returns the combination for the given threshold level
+    pub fn get(&self, level: ThresholdLevel) -> &SignerCombination {
+        match level {
+            ThresholdLevel::Low => &self.low,
+            ThresholdLevel::Medium => &self.medium,
+            ThresholdLevel::High => &self.high,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct AccountData {
+    pub thresholds: Thresholds,
+    pub signers: Vec<Signer>,
+}
+
+/// Computes the minimum signer combination needed for low, medium and high threshold operations.
+///
+/// # Arguments
+/// * `body` - The account's signers and thresholds
+pub fn threshold_breakdown(body: &AccountData) -> ThresholdBreakdown {
+    ThresholdBreakdown {
+        low: min_signer_combination(&body.signers, body.thresholds.low_threshold),
+        medium: min_signer_combination(&body.signers, body.thresholds.med_threshold),
+        high: min_signer_combination(&body.signers, body.thresholds.high_threshold),
+    }
+}
+
+/// Finds the smallest set of signers reaching `threshold` by picking the heaviest ones first.
+///
+/// A threshold of 0 still requires a signature from a signer with a non-zero weight.
+/// Pre-auth transaction signers are left out, as they can only authorize a single known transaction.
+fn min_signer_combination(signers: &[Signer], threshold: u8) -> SignerCombination {
+    let mut active: Vec<&Signer> = signers
+        .iter()
+        .filter(|s| s.weight > 0 && s.signer_type != SignerType::PreauthTx)
+        .collect();
+    active.sort_unstable_by_key(|s| std::cmp::Reverse(s.weight));
+
+    let mut total_weight = 0u32;
+    let mut chosen = Vec::new();
+    let mut reached = false;
+    for signer in &active {
+        total_weight += signer.weight as u32;
+        chosen.push(signer.key.clone());
+        if total_weight >= (threshold as u32).max(1) {
+            reached = true;
+            break;
+        }
+    }
+
+    SignerCombination {
+        threshold,
+        signers: reached.then_some(chosen),
+        total_signers: active.len(),
+    }
+}
+
+/// Flags signers which are not plain ed25519 keys and explains the risk they carry.
+///
+/// # Arguments
+/// * `body` - The account's signers and thresholds
+pub fn special_signers(body: &AccountData) -> Vec<SpecialSigner> {
+    body.signers
+        .iter()
+        .filter(|s| s.weight > 0)
+        .filter_map(|s| {
+            let note = match s.signer_type {
+                SignerType::Ed25519PublicKey => return None,
+                SignerType::Sha256Hash => "anyone who knows the preimage of this hash can sign",
+                SignerType::PreauthTx => {
+                    "pending pre-authorized transaction, anyone can submit it without further signatures. \
+                     If it reaches the high threshold it can change the account's signers and thresholds"
+                }
+                SignerType::Ed25519SignedPayload => {
+                    "signs on behalf of the account when its ed25519 key signs the given payload"
+                }
+            };
+
+            let levels_reached: Vec<ThresholdLevel> = [
+                ThresholdLevel::Low,
+                ThresholdLevel::Medium,
+                ThresholdLevel::High,
+            ]
+            .into_iter()
+            .filter(|level| s.weight >= body.thresholds.get(*level).max(1))
+            .collect();
+
+            Some(SpecialSigner {
+                key: s.key.clone(),
+                signer_type: s.signer_type,
+                weight: s.weight,
+                levels_reached,
+                note: note.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Describes the signer layout and thresholds a classification is based on.
+///
+/// # Arguments
+/// * `body` - The account's signers and thresholds
+/// * `level` - The threshold level the account is classified against
+pub fn signer_evidence(body: &AccountData, level: ThresholdLevel) -> Vec<String> {
+    let breakdown = threshold_breakdown(body);
+    let combination = breakdown.get(level);
+
+    let mut evidence = vec![
+        format!(
+            "thresholds low {}, medium {}, high {}",
+            body.thresholds.low_threshold,
+            body.thresholds.med_threshold,
+            body.thresholds.high_threshold
+        ),
+        match &combination.signers {
+            Some(signers) => format!(
+                "{} of {} signers needed at {} threshold",
+                signers.len(),
+                combination.total_signers,
+                level
+            ),
+            None => format!(
+                "{} signers cannot reach {} threshold",
+                combination.total_signers, level
+            ),
+        },
+    ];
+    evidence.extend(special_signers(body).iter().map(|s| {
+        format!(
+            "{} signer {} with weight {}",
+            s.signer_type, s.key, s.weight
+        )
+    }));
+    evidence
+}
+
+/// Checks if a "G" account is a multisig/"decentralized" account by analyzing its signers and thresholds.
+///
+/// Returns:
+/// - `AccountType::HashX` if a hash-x signer alone has enough weight for `level` operations
+/// - `AccountType::HotWallet` if any single signer has enough weight for `level` operations
+/// - `AccountType::Multisig` if multiple signers are required
+/// - `AccountType::Deactivated` if the account's signers cannot reach the threshold
+///
+/// # Arguments
+/// * `body` - The account's signers and thresholds
+/// * `level` - The threshold level to classify against
+pub fn check_if_centralized(body: &AccountData, level: ThresholdLevel) -> AccountType {
+    let breakdown = threshold_breakdown(body);
+
+    let threshold = body.thresholds.get(level).max(1);
+    if body
+        .signers
+        .iter()
+        .any(|s| s.signer_type == SignerType::Sha256Hash && s.weight >= threshold)
+    {
+        return AccountType::HashX;
+    }
+
+    match &breakdown.get(level).signers {
+        None => AccountType::Deactivated,
+        Some(signers) if signers.len() == 1 => AccountType::HotWallet,
+        Some(_) => {
+            let required = |c: &SignerCombination| c.signers.as_ref().map(|s| s.len() as u8);
+            AccountType::Multisig(MultisigLevels {
+                level,
+                low: required(&breakdown.low),
+                medium: required(&breakdown.medium),
+                high: required(&breakdown.high),
+                total: breakdown.low.total_signers as u8,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signer(key: &str, signer_type: SignerType, weight: u8) -> Signer {
+        Signer {
+            key: key.to_string(),
+            signer_type,
+            weight,
+        }
+    }
+
+    fn account(thresholds: [u8; 3], signers: Vec<Signer>) -> AccountData {
+        AccountData {
+            thresholds: Thresholds {
+                low_threshold: thresholds[0],
+                med_threshold: thresholds[1],
+                high_threshold: thresholds[2],
+            },
+            signers,
+        }
+    }
+
+    #[test]
+    fn breaks_down_signer_combinations_per_threshold() {
+        let data = account(
+            [0, 2, 5],
+            vec![
+                signer("GA", SignerType::Ed25519PublicKey, 1),
+                signer("GB", SignerType::Ed25519PublicKey, 2),
+                signer("GC", SignerType::Ed25519PublicKey, 0),
+                signer("T", SignerType::PreauthTx, 10),
+            ],
+        );
+        let breakdown = threshold_breakdown(&data);
+
+        // A threshold of 0 still needs one signature, from the heaviest signer
+        assert_eq!(breakdown.low.signers, Some(vec!["GB".to_string()]));
+        assert_eq!(breakdown.medium.signers, Some(vec!["GB".to_string()]));
+        // The pre-auth transaction and the zero weight signer are left out
+        assert_eq!(breakdown.high.signers, None);
+        assert_eq!(breakdown.high.total_signers, 2);
+        assert_eq!(breakdown.high.to_string(), "unreachable");
+
+        let data = account([1, 1, 3], data.signers);
+        let high = threshold_breakdown(&data).high;
+        assert_eq!(high.signers, Some(vec!["GB".to_string(), "GA".to_string()]));
+        assert_eq!(high.to_string(), "2 of 2");
+    }
+
+    #[test]
+    fn classifies_against_the_selected_threshold() {
+        // 1-of-3 for payments, 2-of-3 for changing signers
+        let signers = ["GA", "GB", "GC"]
+            .map(|key| signer(key, SignerType::Ed25519PublicKey, 1))
+            .to_vec();
+        let data = account([1, 1, 2], signers.clone());
+
+        assert_eq!(
+            check_if_centralized(&data, ThresholdLevel::Medium),
+            AccountType::HotWallet
+        );
+        assert_eq!(
+            check_if_centralized(&data, ThresholdLevel::High),
+            AccountType::Multisig(MultisigLevels {
+                level: ThresholdLevel::High,
+                low: Some(1),
+                medium: Some(1),
+                high: Some(2),
+                total: 3,
+            })
+        );
+
+        // A threshold of 0 is met by a single signature
+        let data = account([0, 0, 0], signers.clone());
+        assert_eq!(
+            check_if_centralized(&data, ThresholdLevel::High),
+            AccountType::HotWallet
+        );
+
+        let data = account([1, 1, 4], signers);
+        assert_eq!(
+            check_if_centralized(&data, ThresholdLevel::High),
+            AccountType::Deactivated
+        );
+    }
+
+    #[test]
+    fn hash_x_signer_reaching_the_threshold_has_control() {
+        let signers = vec![
+            signer("GA", SignerType::Ed25519PublicKey, 1),
+            signer("GB", SignerType::Ed25519PublicKey, 1),
+            signer("X", SignerType::Sha256Hash, 2),
+        ];
+        let data = account([1, 2, 3], signers);
+
+        assert_eq!(
+            check_if_centralized(&data, ThresholdLevel::Medium),
+            AccountType::HashX
+        );
+        assert!(matches!(
+            check_if_centralized(&data, ThresholdLevel::High),
+            AccountType::Multisig(_)
+        ));
+    }
+
+    #[test]
+    fn flags_special_signers() {
+        let data = account(
+            [1, 2, 3],
+            vec![
+                signer("GA", SignerType::Ed25519PublicKey, 3),
+                signer("X", SignerType::Sha256Hash, 2),
+                signer("T", SignerType::PreauthTx, 3),
+                signer("P", SignerType::Ed25519SignedPayload, 0),
+            ],
+        );
+        let special = special_signers(&data);
+
+        assert_eq!(special.len(), 2);
+        assert_eq!(special[0].signer_type, SignerType::Sha256Hash);
+        assert_eq!(
+            special[0].levels_reached,
+            [ThresholdLevel::Low, ThresholdLevel::Medium]
+        );
+        assert!(special[0].note.contains("preimage"));
+        assert_eq!(special[1].signer_type, SignerType::PreauthTx);
+        assert_eq!(special[1].levels_reached.len(), 3);
+        assert!(special[1].note.contains("signers and thresholds"));
+    }
+}
@@ -1,13 +1,87 @@
-use std::collections::HashSet;
+use core::fmt;
+use serde::Serialize;
 use std::str::FromStr;
-use stellar_xdr::curr::{AccountId, ScAddress, ScString, ScSymbol, ScVal, ScVec, StringM};
+use stellar_rpc_client::GetLedgerEntriesResponse;
+use stellar_xdr::curr::{
+    AccountId, ContractDataEntry, LedgerEntryData, Limits, ReadXdr, ScAddress, ScMapEntry,
+    ScString, ScSymbol, ScVal, ScVec, StringM,
+};
 
-use crate::error::Error;
+use crate::{
+    error::Error,
+    role_hints::{is_inactive_path, key_score},
+    ttl::TtlStatus,
+};
 
-#[derive(Debug)]
+/// Maximum length of a Soroban symbol
+const SYMBOL_MAX_LEN: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum AddressType {
     EOA(String),
-    Contract,
+    Contract(String),
+}
+
+impl fmt::Display for AddressType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AddressType::EOA(addr) | AddressType::Contract(addr) => write!(f, "{}", addr),
+        }
+    }
+}
+
+/// Address found inside a storage value
+#[derive(Debug, Clone, Serialize)]
+pub struct ValueAddress {
+    /// Location of the address inside the value, `$` being the value itself
+    pub path: String,
+    pub address: AddressType,
+}
+
+/// Admin decoded out of a storage value
+#[derive(Debug, Clone)]
+pub struct DecodedAdmin {
+    /// Address considered to be the admin
+    pub admin: AddressType,
+    /// Every address found in the value
+    pub addresses: Vec<ValueAddress>,
+}
+
+/// Contract storage an entry was found in, in lookup order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum Durability {
+    Instance,
+    Persistent,
+    Temporary,
+}
+
+impl fmt::Display for Durability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Durability::Instance => write!(f, "instance"),
+            Durability::Persistent => write!(f, "persistent"),
+            Durability::Temporary => write!(f, "temporary"),
+        }
+    }
+}
+
+/// Contract storage entry matching one of the looked up keys
+#[derive(Debug)]
+pub struct StorageEntry {
+    pub key: ScVal,
+    pub val: ScVal,
+    pub durability: Durability,
+    /// TTL of the entry, or of the contract instance for instance storage entries
+    pub ttl: Option<TtlStatus>,
+}
+
+/// Contract instance storage
+#[derive(Debug)]
+pub struct InstanceStorage {
+    pub storage: Vec<ScMapEntry>,
+    /// TTL of the contract instance
+    pub ttl: Option<TtlStatus>,
 }
 
 /// Possible formats for the admin storage key
@@ -18,25 +92,175 @@ enum KeyType {
     String,
 }
 
-/// Generates a set of possible storage keys for contract lookup.
+/// Generates the possible storage keys for contract lookup, in order of preference.
 ///
-/// For each input key, generates variations in all possible formats:
+/// For each input key, in the given order, generates variations in all possible formats:
 /// - Enum variant format
 /// - Symbol format
 /// - String format
 ///
+/// Keys with arguments can only be enum variants, so only that format is generated for them.
+/// Variations which do not fit in a symbol are skipped for the symbol based formats.
+///
 /// # Arguments
 /// * `keys` - Vector of key strings to generate variations for
-pub fn possible_keys(keys: Vec<String>) -> HashSet<ScVal> {
-    let mut ret = HashSet::new();
+/// * `args` - Enum variant arguments, see `parse_key_expression`
+pub fn possible_keys(keys: Vec<String>, args: &[ScVal]) -> Vec<ScVal> {
+    let mut ret = Vec::new();
     for k in keys {
-        ret.insert(format_key(&k, KeyType::EnumVariant));
-        ret.insert(format_key(&k, KeyType::String));
-        ret.insert(format_key(&k, KeyType::Symbol));
+        let formats = if args.is_empty() {
+            vec![
+                format_key(&k, KeyType::EnumVariant),
+                format_key(&k, KeyType::Symbol),
+                format_key(&k, KeyType::String),
+            ]
+        } else {
+            vec![get_enum_variant_key(&k, args)]
+        };
+        for key in formats.into_iter().flatten() {
+            if !ret.contains(&key) {
+                ret.push(key);
+            }
+        }
     }
     ret
 }
 
+/// Splits a key expression such as `Role:GABC...` or `Admins:0` into the
+/// variant name and its arguments.
+///
+/// Arguments are separated by `:`, except inside double quotes, and parsed as:
+/// - `G...`/`C...` strkeys as addresses
+/// - `true`/`false` as booleans
+/// - integers as `u32`, unless suffixed with `u64`, `i32`, `i64`, `u128` or `i128`
+/// - double quoted text as strings
+/// - anything else as symbols
+///
+/// The variant name must fit in a Soroban symbol.
+///
+/// # Arguments
+/// * `expr` - The key expression
+pub fn parse_key_expression(expr: &str) -> Result<(String, Vec<ScVal>), Error> {
+    let mut parts =
+        split_key_expression(expr).ok_or_else(|| Error::MalformedKey(expr.to_string()))?;
+    let args = parts.split_off(1);
+    let name = parts.pop().unwrap_or_default();
+    if name.is_empty() {
+        return Err(Error::MalformedKey(expr.to_string()));
+    }
+    if name.len() > SYMBOL_MAX_LEN {
+        return Err(Error::KeyTooLong(name));
+    }
+    let args = args
+        .iter()
+        .map(|arg| parse_key_arg(arg).ok_or_else(|| Error::MalformedKey(expr.to_string())))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((name, args))
+}
+
+/// Splits a key expression on `:`, keeping double quoted arguments whole.
+///
+/// Returns `None` if a quote is left open.
+fn split_key_expression(expr: &str) -> Option<Vec<String>> {
+    let mut parts = vec![String::new()];
+    let mut quoted = false;
+    for c in expr.chars() {
+        match c {
+            ':' if !quoted => parts.push(String::new()),
+            c => {
+                if c == '"' {
+                    quoted = !quoted;
+                }
+                parts.last_mut()?.push(c);
+            }
+        }
+    }
+    (!quoted).then_some(parts)
+}
+
+/// Parses a storage key given as base64 XDR or as the stellar-xdr JSON representation of an ScVal.
+///
+/// # Arguments
+/// * `raw` - The encoded key
+pub fn parse_raw_key(raw: &str) -> Result<ScVal, Error> {
+    ScVal::from_xdr_base64(raw, Limits::none())
+        .ok()
+        .or_else(|| serde_json::from_str(raw).ok())
+        .ok_or_else(|| Error::MalformedKey(raw.to_string()))
+}
+
+/// Parses a single enum variant argument, see `parse_key_expression`.
+fn parse_key_arg(arg: &str) -> Option<ScVal> {
+    if arg.is_empty() {
+        return None;
+    }
+    if let Ok(addr) = ScAddress::from_str(arg) {
+        return Some(ScVal::Address(addr));
+    }
+    if let Some(text) = arg.strip_prefix('"').and_then(|a| a.strip_suffix('"')) {
+        return Some(ScVal::String(ScString::from(StringM::try_from(text).ok()?)));
+    }
+    match arg {
+        "true" => return Some(ScVal::Bool(true)),
+        "false" => return Some(ScVal::Bool(false)),
+        _ => (),
+    }
+
+    let (digits, suffix) = ["u32", "i32", "u64", "i64", "u128", "i128"]
+        .iter()
+        .find_map(|suffix| arg.strip_suffix(suffix).map(|d| (d, *suffix)))
+        .unwrap_or((arg, ""));
+    let unsigned = digits.strip_prefix('-').unwrap_or(digits);
+    if unsigned.is_empty() || !unsigned.chars().all(|c| c.is_ascii_digit()) {
+        return Some(ScVal::Symbol(ScSymbol::from(StringM::try_from(arg).ok()?)));
+    }
+    match suffix {
+        "" | "u32" => digits.parse().ok().map(ScVal::U32),
+        "i32" => digits.parse().ok().map(ScVal::I32),
+        "u64" => digits.parse().ok().map(ScVal::U64),
+        "i64" => digits.parse().ok().map(ScVal::I64),
+        "u128" => digits.parse::<u128>().ok().map(ScVal::from),
+        "i128" => digits.parse::<i128>().ok().map(ScVal::from),
+        _ => None,
+    }
+}
+
+/// Extracts a readable name from a storage key.
+///
+/// Symbols and strings are used as is, enum variants use the variant name.
+///
+/// # Arguments
+/// * `key` - The storage key
+pub fn key_name(key: &ScVal) -> Option<String> {
+    match key {
+        ScVal::Symbol(s) => Some(s.to_utf8_string_lossy()),
+        ScVal::String(s) => Some(s.to_utf8_string_lossy()),
+        ScVal::Vec(Some(v)) => v.first().and_then(key_name),
+        _ => None,
+    }
+}
+
+/// Names the format of a storage key.
+///
+/// # Arguments
+/// * `key` - The storage key
+pub fn key_format(key: &ScVal) -> &'static str {
+    match key {
+        ScVal::Symbol(_) => "symbol",
+        ScVal::String(_) => "string",
+        ScVal::Vec(Some(v)) if matches!(v.first(), Some(ScVal::Symbol(_))) => "enum variant",
+        _ => "raw key",
+    }
+}
+
+/// Describes a storage key for display, falling back to its JSON form.
+///
+/// # Arguments
+/// * `key` - The storage key
+pub fn describe_key(key: &ScVal) -> String {
+    key_name(key).unwrap_or_else(|| serde_json::to_string(key).unwrap_or_default())
+}
+
 /// Wraps an AccountId into an AddressType
 ///
 /// # Arguments
@@ -45,41 +269,368 @@ pub fn wrap_eoa(id: AccountId) -> AddressType {
     AddressType::EOA(AccountId::to_string(&id))
 }
 
-/// Converts ScVal into AddressType
+/// Converts an ScAddress into AddressType
+///
+/// # Arguments
+/// * `addr` - The ScAddress to convert
+pub fn wrap_address(addr: &ScAddress) -> AddressType {
+    match addr {
+        ScAddress::Account(id) => wrap_eoa(id.clone()),
+        ScAddress::Contract(_) => AddressType::Contract(addr.to_string()),
+    }
+}
+
+/// Decodes the admin out of a storage value.
+///
+/// The value can be a bare address, or any combination of options, vectors, maps and
+/// structs holding addresses. Every address is returned along with the admin, which is the
+/// best match among the active ones (pending admins are skipped when possible).
 ///
 /// # Arguments
 /// * `val` - The ScVal to decode
-pub fn decode_admin_value(val: &ScVal) -> Result<AddressType, Error> {
-    let addr = match val {
-        ScVal::Address(addr) => addr.clone(),
-        _ => return Err(Error::WrongStorageType),
-    };
+pub fn decode_admin_value(val: &ScVal) -> Result<DecodedAdmin, Error> {
+    let mut addresses = Vec::new();
+    find_addresses(val, "$".to_string(), &mut addresses);
+
+    let is_active = |a: &&ValueAddress| !is_inactive_path(&a.path);
+    let admin = addresses
+        .iter()
+        .filter(is_active)
+        .min_by_key(|a| (std::cmp::Reverse(key_score(&a.path)), a.path.len()))
+        .or(addresses.first())
+        .ok_or(Error::WrongStorageType)?
+        .address
+        .clone();
+
+    Ok(DecodedAdmin { admin, addresses })
+}
 
-    if let ScAddress::Account(id) = addr {
-        Ok(wrap_eoa(id))
-    } else {
-        Ok(AddressType::Contract)
+/// Walks a value recursively, collecting every address with its path.
+///
+/// # Arguments
+/// * `val` - The value to walk
+/// * `path` - Path of `val` inside the storage value
+/// * `found` - Collected addresses
+fn find_addresses(val: &ScVal, path: String, found: &mut Vec<ValueAddress>) {
+    match val {
+        ScVal::Address(addr) => found.push(ValueAddress {
+            path,
+            address: wrap_address(addr),
+        }),
+        ScVal::Vec(Some(items)) => {
+            for (i, item) in items.iter().enumerate() {
+                find_addresses(item, format!("{}[{}]", path, i), found);
+            }
+        }
+        ScVal::Map(Some(entries)) => {
+            for entry in entries.iter() {
+                let field = match &entry.key {
+                    ScVal::Symbol(s) => format!("{}.{}", path, s.to_utf8_string_lossy()),
+                    key => format!("{}[{}]", path, describe_key(key)),
+                };
+                find_addresses(&entry.key, format!("{}{{key}}", field), found);
+                find_addresses(&entry.val, field, found);
+            }
+        }
+        _ => (),
     }
 }
 
 /// Formats a key string into the specified ScVal format.
 ///
+/// Returns `None` if the key does not fit in the format.
+///
 /// # Arguments
 /// * `key` - The key string to format
 /// * `key_type` - The desired format type
-fn format_key(key: &str, key_type: KeyType) -> ScVal {
+fn format_key(key: &str, key_type: KeyType) -> Option<ScVal> {
     match key_type {
-        KeyType::EnumVariant => get_enum_variant_key(key),
-        KeyType::Symbol => ScVal::Symbol(ScSymbol::from(StringM::from_str(key).unwrap())),
-        KeyType::String => ScVal::String(ScString::from(StringM::from_str(key).unwrap())),
+        KeyType::EnumVariant => get_enum_variant_key(key, &[]),
+        KeyType::Symbol => Some(ScVal::Symbol(ScSymbol::from(StringM::try_from(key).ok()?))),
+        KeyType::String => Some(ScVal::String(ScString::from(StringM::try_from(key).ok()?))),
     }
 }
 
-fn get_enum_variant_key(key: &str) -> ScVal {
-    ScVal::Vec(Some(
-        ScVec::try_from(vec![ScVal::Symbol(ScSymbol::from(ScSymbol::from(
-            StringM::from_str(key).unwrap(),
-        )))])
-        .unwrap(),
-    ))
+/// Builds an enum variant key, the variant name followed by its arguments.
+fn get_enum_variant_key(key: &str, args: &[ScVal]) -> Option<ScVal> {
+    let mut items = vec![ScVal::Symbol(ScSymbol::from(StringM::try_from(key).ok()?))];
+    items.extend_from_slice(args);
+    Some(ScVal::Vec(Some(ScVec::try_from(items).ok()?)))
+}
+
+/// Decodes the contract data entries of a `getLedgerEntries` response.
+///
+/// A response without entries means none of the keys exist.
+///
+/// # Arguments
+/// * `response` - The RPC response
+/// * `durability` - The storage the entries were looked up in
+pub fn decode_storage_entries(
+    response: &GetLedgerEntriesResponse,
+    durability: Durability,
+) -> Result<Vec<StorageEntry>, Error> {
+    let mut found = Vec::new();
+    for entry in response.entries.iter().flatten() {
+        let data = LedgerEntryData::from_xdr_base64(&entry.xdr, Limits::none())
+            .map_err(|e| Error::MalformedRpcResponse(e.to_string()))?;
+        let LedgerEntryData::ContractData(data) = data else {
+            return Err(Error::MalformedRpcResponse(format!(
+                "expected contract data entry for key {}",
+                entry.key
+            )));
+        };
+        found.push(StorageEntry {
+            key: data.key,
+            val: data.val,
+            durability,
+            ttl: entry
+                .live_until_ledger_seq_ledger_seq
+                .map(|l| TtlStatus::new(l, response.latest_ledger as u32)),
+        });
+    }
+    Ok(found)
+}
+
+/// Decodes the contract instance entry of a `getLedgerEntries` response.
+///
+/// # Arguments
+/// * `response` - The RPC response
+/// * `contract_id` - The contract the instance was looked up for
+pub fn decode_instance_storage(
+    response: &GetLedgerEntriesResponse,
+    contract_id: &str,
+) -> Result<InstanceStorage, Error> {
+    let entry = response
+        .entries
+        .iter()
+        .flatten()
+        .next()
+        .ok_or_else(|| Error::ContractNotFound(contract_id.to_string()))?;
+
+    let data = LedgerEntryData::from_xdr_base64(&entry.xdr, Limits::none())
+        .map_err(|e| Error::MalformedRpcResponse(e.to_string()))?;
+    let instance = match data {
+        LedgerEntryData::ContractData(ContractDataEntry {
+            val: ScVal::ContractInstance(instance),
+            ..
+        }) => instance,
+        _ => {
+            return Err(Error::MalformedRpcResponse(
+                "contract instance entry is not a contract instance".to_string(),
+            ))
+        }
+    };
+
+    Ok(InstanceStorage {
+        storage: instance.storage.map(|s| s.0.to_vec()).unwrap_or_default(),
+        ttl: entry
+            .live_until_ledger_seq_ledger_seq
+            .map(|l| TtlStatus::new(l, response.latest_ledger as u32)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_fixtures::{ledger_entries, ttl_entry};
+    use stellar_xdr::curr::{
+        ContractDataDurability, ExtensionPoint, Hash, ScContractInstance, ScMap, WriteXdr,
+    };
+
+    fn contract_data(key: ScVal, val: ScVal) -> String {
+        LedgerEntryData::ContractData(ContractDataEntry {
+            ext: ExtensionPoint::V0,
+            contract: ScAddress::Contract(Hash([1; 32])),
+            key,
+            durability: ContractDataDurability::Persistent,
+            val,
+        })
+        .to_xdr_base64(Limits::none())
+        .unwrap()
+    }
+
+    fn symbol(s: &str) -> ScVal {
+        ScVal::Symbol(ScSymbol::from(StringM::try_from(s).unwrap()))
+    }
+
+    #[test]
+    fn null_entries_mean_no_match() {
+        let response: GetLedgerEntriesResponse =
+            serde_json::from_str(r#"{"entries":null,"latestLedger":100}"#).unwrap();
+
+        let entries = decode_storage_entries(&response, Durability::Persistent).unwrap();
+        assert!(entries.is_empty());
+        assert!(matches!(
+            decode_instance_storage(&response, "CA"),
+            Err(Error::ContractNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn invalid_entry_xdr_is_an_error() {
+        let response = ledger_entries(&["not base64 xdr"]);
+
+        assert!(matches!(
+            decode_storage_entries(&response, Durability::Persistent),
+            Err(Error::MalformedRpcResponse(_))
+        ));
+        assert!(matches!(
+            decode_instance_storage(&response, "CA"),
+            Err(Error::MalformedRpcResponse(_))
+        ));
+    }
+
+    #[test]
+    fn unexpected_entry_type_is_an_error() {
+        let response = ledger_entries(&[&ttl_entry()]);
+
+        assert!(matches!(
+            decode_storage_entries(&response, Durability::Temporary),
+            Err(Error::MalformedRpcResponse(_))
+        ));
+    }
+
+    #[test]
+    fn instance_entry_without_instance_is_an_error() {
+        let xdr = contract_data(ScVal::LedgerKeyContractInstance, ScVal::Void);
+
+        assert!(matches!(
+            decode_instance_storage(&ledger_entries(&[&xdr]), "CA"),
+            Err(Error::MalformedRpcResponse(_))
+        ));
+    }
+
+    #[test]
+    fn decodes_storage_entries() {
+        let admin = ScVal::Address(ScAddress::Contract(Hash([2; 32])));
+        let xdr = contract_data(symbol("Admin"), admin.clone());
+
+        let entries =
+            decode_storage_entries(&ledger_entries(&[&xdr]), Durability::Persistent).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].key, symbol("Admin"));
+        assert_eq!(entries[0].val, admin);
+        let ttl = entries[0].ttl.as_ref().unwrap();
+        assert_eq!(ttl.live_until_ledger, 200);
+        assert_eq!(ttl.remaining_ledgers, 100);
+    }
+
+    #[test]
+    fn decodes_instance_storage() {
+        let instance = ScVal::ContractInstance(ScContractInstance {
+            executable: stellar_xdr::curr::ContractExecutable::StellarAsset,
+            storage: Some(ScMap::sorted_from(vec![(symbol("Admin"), ScVal::Bool(true))]).unwrap()),
+        });
+        let xdr = contract_data(ScVal::LedgerKeyContractInstance, instance);
+
+        let storage = decode_instance_storage(&ledger_entries(&[&xdr]), "CA").unwrap();
+        assert_eq!(storage.storage.len(), 1);
+        assert_eq!(storage.storage[0].key, symbol("Admin"));
+    }
+
+    #[test]
+    fn skips_pending_admins_by_whole_words() {
+        let address = |n| ScVal::Address(ScAddress::Contract(Hash([n; 32])));
+        let config = ScVal::Map(Some(
+            ScMap::sorted_from(vec![
+                (symbol("pending_admin"), address(1)),
+                (symbol("renewal_admin"), address(2)),
+            ])
+            .unwrap(),
+        ));
+
+        let decoded = decode_admin_value(&config).unwrap();
+        assert_eq!(decoded.addresses.len(), 2);
+        assert_eq!(
+            decoded.admin,
+            AddressType::Contract(ScAddress::Contract(Hash([2; 32])).to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_keys_longer_than_a_symbol() {
+        let key = "a".repeat(SYMBOL_MAX_LEN + 1);
+
+        assert!(matches!(
+            parse_key_expression(&key),
+            Err(Error::KeyTooLong(_))
+        ));
+        assert!(parse_key_expression(&"a".repeat(SYMBOL_MAX_LEN)).is_ok());
+        assert!(matches!(
+            parse_key_expression(":0"),
+            Err(Error::MalformedKey(_))
+        ));
+    }
+
+    #[test]
+    fn parses_key_arguments() {
+        let account = "GBXGQJWVLWOYHFLVTKWV5FGHA3LNYY2JQKM7OAJAUEQFU6LPCSEFVXON";
+        let (name, args) = parse_key_expression(&format!("Role:{}", account)).unwrap();
+        assert_eq!(name, "Role");
+        assert_eq!(
+            args,
+            [ScVal::Address(ScAddress::from_str(account).unwrap())]
+        );
+
+        let string = |s: &str| ScVal::String(ScString::from(StringM::try_from(s).unwrap()));
+        let (_, args) = parse_key_expression(r#"Role:"a:b":"""#).unwrap();
+        assert_eq!(args, [string("a:b"), string("")]);
+
+        let (_, args) = parse_key_expression("Flag:true:false").unwrap();
+        assert_eq!(args, [ScVal::Bool(true), ScVal::Bool(false)]);
+
+        let (_, args) = parse_key_expression("Admins:0:7u64:-1i32:5i128").unwrap();
+        assert_eq!(
+            args,
+            [
+                ScVal::U32(0),
+                ScVal::U64(7),
+                ScVal::I32(-1),
+                ScVal::from(5i128)
+            ]
+        );
+
+        let (_, args) = parse_key_expression("Role:Minter").unwrap();
+        assert_eq!(args, [symbol("Minter")]);
+
+        for malformed in [r#"Role:"a:b"#, "Admins:-1", "Admins:99999999999", "Role:"] {
+            assert!(
+                matches!(parse_key_expression(malformed), Err(Error::MalformedKey(_))),
+                "{}",
+                malformed
+            );
+        }
+    }
+
+    #[test]
+    fn skips_key_variations_longer_than_a_symbol() {
+        let long = "a".repeat(SYMBOL_MAX_LEN + 1);
+
+        let keys = possible_keys(vec![long.clone()], &[]);
+        assert_eq!(keys.len(), 1);
+        assert!(matches!(keys.first(), Some(ScVal::String(_))));
+        assert!(possible_keys(vec![long], &[ScVal::U32(0)]).is_empty());
+    }
+
+    #[test]
+    fn parses_raw_keys() {
+        let key = ScVal::Vec(Some(
+            ScVec::try_from(vec![symbol("Admin"), ScVal::U64(1)]).unwrap(),
+        ));
+
+        let xdr = key.to_xdr_base64(Limits::none()).unwrap();
+        assert_eq!(parse_raw_key(&xdr).unwrap(), key);
+        assert_eq!(
+            parse_raw_key(r#"{"vec":[{"symbol":"Admin"},{"u64":1}]}"#).unwrap(),
+            key
+        );
+
+        for malformed in ["", "Admin", "AAAA", r#"{"symbol":1}"#, r#"{"vec":"#] {
+            assert!(
+                matches!(parse_raw_key(malformed), Err(Error::MalformedKey(_))),
+                "{}",
+                malformed
+            );
+        }
+    }
 }
@@ -0,0 +1,33 @@
+use stellar_rpc_client::GetLedgerEntriesResponse;
+use stellar_xdr::curr::{Hash, LedgerEntryData, Limits, TtlEntry, WriteXdr};
+
+/// Builds a `getLedgerEntries` response at ledger 100, with every entry live until ledger 200.
+///
+/// # Arguments
+/// * `entries` - Base64 XDR of the `LedgerEntryData` of each entry
+pub fn ledger_entries(entries: &[&str]) -> GetLedgerEntriesResponse {
+    let entries: Vec<String> = entries
+        .iter()
+        .map(|xdr| {
+            format!(
+                r#"{{"key":"AAAA","xdr":"{}","lastModifiedLedgerSeq":90,"liveUntilLedgerSeq":"200"}}"#,
+                xdr
+            )
+        })
+        .collect();
+    serde_json::from_str(&format!(
+        r#"{{"entries":[{}],"latestLedger":100}}"#,
+        entries.join(",")
+    ))
+    .unwrap()
+}
+
+/// Base64 XDR of a TTL entry, which is never the entry a lookup expects
+pub fn ttl_entry() -> String {
+    LedgerEntryData::Ttl(TtlEntry {
+        key_hash: Hash([0; 32]),
+        live_until_ledger_seq: 200,
+    })
+    .to_xdr_base64(Limits::none())
+    .unwrap()
+}
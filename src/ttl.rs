@@ -0,0 +1,60 @@
+use core::fmt;
+use serde::Serialize;
+
+/// Expected ledger close time on public networks, in seconds
+const LEDGER_CLOSE_SECS: u64 = 5;
+
+/// Time to live of a contract storage entry.
+#[derive(Debug, Clone, Serialize)]
+pub struct TtlStatus {
+    /// Last ledger the entry is live at
+    pub live_until_ledger: u32,
+    /// Latest ledger known by the RPC when the entry was fetched
+    pub latest_ledger: u32,
+    /// Ledgers left before the entry gets archived, 0 once archived
+    pub remaining_ledgers: u32,
+    /// Estimated time left before the entry gets archived, in seconds
+    pub remaining_secs: u64,
+    /// Whether the entry is archived and needs to be restored before the contract can use it
+    pub archived: bool,
+}
+
+impl TtlStatus {
+    /// Computes the status of an entry.
+    ///
+    /// # Arguments
+    /// * `live_until_ledger` - Last ledger the entry is live at
+    /// * `latest_ledger` - Latest ledger known by the RPC
+    pub fn new(live_until_ledger: u32, latest_ledger: u32) -> Self {
+        let remaining_ledgers = live_until_ledger.saturating_sub(latest_ledger);
+        Self {
+            live_until_ledger,
+            latest_ledger,
+            remaining_ledgers,
+            remaining_secs: remaining_ledgers as u64 * LEDGER_CLOSE_SECS,
+            archived: live_until_ledger < latest_ledger,
+        }
+    }
+}
+
+impl fmt::Display for TtlStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.archived {
+            return write!(
+                f,
+                "archived since ledger {}, needs restore",
+                self.live_until_ledger + 1
+            );
+        }
+
+        let secs = self.remaining_secs;
+        write!(
+            f,
+            "live until ledger {} (~{}d {}h {}m left)",
+            self.live_until_ledger,
+            secs / 86400,
+            secs % 86400 / 3600,
+            secs % 3600 / 60
+        )
+    }
+}
@@ -0,0 +1,622 @@
+use chrono::{DateTime, Datelike, NaiveDate, TimeDelta, Timelike, Utc};
+use core::fmt;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    account_type::{AccountType, Confidence},
+    history::Truncation,
+};
+
+/// Default number of seconds within which consecutive transactions are back-to-back
+pub const DEFAULT_BACK_TO_BACK_SECS: i64 = 60;
+/// Minimum number of back-to-back transactions in a row forming a burst
+const BURST_MIN_TXS: usize = 3;
+/// Share of back-to-back gaps from which the account looks automated
+const BACK_TO_BACK_SHARE: f64 = 0.25;
+/// Median gap, in seconds, under which the account looks automated
+const MEDIAN_GAP_SECS: u64 = 3600;
+/// Bursts per active day from which bursts look routine rather than one-off
+const BURSTS_PER_DAY: f64 = 1.0;
+/// Number of distinct UTC hours with activity from which the account looks round-the-clock
+const ROUND_THE_CLOCK_HOURS: usize = 16;
+/// Minimum number of transactions for the time of day spread to mean anything
+const MIN_TXS_FOR_SPREAD: usize = 24;
+/// Number of automation signals from which the account is classified as a hot wallet
+const MIN_SIGNALS: usize = 2;
+/// Minimum number of transactions for the history to tell anything
+const MIN_TXS: usize = 2;
+/// Number of transactions from which the verdict can be more than a low confidence guess
+const MIN_TXS_CONFIDENT: usize = 20;
+
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Transaction pattern figures for an account.
+#[derive(Serialize, Debug, Clone)]
+pub struct TxFrequency {
+    /// Number of transactions sent or paid for by the account
+    pub tx_count: usize,
+    /// Minimum number of seconds between two consecutive transactions, if there are at least 2
+    pub min_gap_secs: Option<u64>,
+    /// 10th percentile of the gaps between consecutive transactions, in seconds
+    pub p10_gap_secs: Option<u64>,
+    /// Median gap between consecutive transactions, in seconds
+    pub median_gap_secs: Option<u64>,
+    /// 90th percentile of the gaps between consecutive transactions, in seconds
+    pub p90_gap_secs: Option<u64>,
+    /// Gap, in seconds, up to which consecutive transactions are back-to-back
+    pub back_to_back_secs: u64,
+    /// Share of back-to-back gaps
+    pub back_to_back_share: Option<f64>,
+    /// Runs of at least 3 back-to-back transactions
+    pub bursts: usize,
+    /// Number of UTC days with at least one transaction
+    pub active_days: usize,
+    /// Largest number of transactions in a single UTC day
+    pub max_txs_per_day: usize,
+    /// Number of transactions in each UTC hour of the day
+    pub hours: [usize; 24],
+    /// Number of transactions on each weekday, starting on Monday
+    pub weekdays: [usize; 7],
+    /// Patterns pointing to a signer which is always online
+    pub signals: Vec<String>,
+    /// Why older transactions were left out, if they were
+    pub truncated: Option<Truncation>,
+}
+
+impl TxFrequency {
+    /// Computes the pattern figures of a transaction history.
+    ///
+    /// Gaps are measured between ledger close times, so they do not depend on the
+    /// ledger rate of the network.
+    ///
+    /// # Arguments
+    /// * `txs` - Close time of the ledger of each transaction, oldest first
+    /// * `back_to_back` - Gap up to which consecutive transactions are back-to-back
+    pub fn from_history(txs: &[DateTime<Utc>], back_to_back: TimeDelta) -> Self {
+        let back_to_back_secs = secs(back_to_back);
+        let mut gaps: Vec<u64> = txs.windows(2).map(|w| secs(w[1] - w[0])).collect();
+
+        let mut bursts = 0;
+        let mut run = 1;
+        for gap in &gaps {
+            if *gap <= back_to_back_secs {
+                run += 1;
+                if run == BURST_MIN_TXS {
+                    bursts += 1;
+                }
+            } else {
+                run = 1;
+            }
+        }
+        let back_to_back = gaps.iter().filter(|g| **g <= back_to_back_secs).count();
+        let back_to_back_share =
+            (!gaps.is_empty()).then(|| back_to_back as f64 / gaps.len() as f64);
+
+        let mut days: HashMap<NaiveDate, usize> = HashMap::new();
+        let mut hours = [0; 24];
+        let mut weekdays = [0; 7];
+        for created_at in txs {
+            *days.entry(created_at.date_naive()).or_default() += 1;
+            hours[created_at.hour() as usize] += 1;
+            weekdays[created_at.weekday().num_days_from_monday() as usize] += 1;
+        }
+
+        gaps.sort_unstable();
+        let mut frequency = TxFrequency {
+            tx_count: txs.len(),
+            min_gap_secs: gaps.first().copied(),
+            p10_gap_secs: percentile(&gaps, 10),
+            median_gap_secs: percentile(&gaps, 50),
+            p90_gap_secs: percentile(&gaps, 90),
+            back_to_back_secs,
+            back_to_back_share,
+            bursts,
+            active_days: days.len(),
+            max_txs_per_day: days.values().copied().max().unwrap_or_default(),
+            hours,
+            weekdays,
+            signals: vec![],
+            truncated: None,
+        };
+        frequency.signals = frequency.automation_signals();
+        frequency
+    }
+
+    /// Average number of bursts per day with activity
+    pub fn bursts_per_day(&self) -> f64 {
+        if self.active_days == 0 {
+            return 0.0;
+        }
+        self.bursts as f64 / self.active_days as f64
+    }
+
+    /// Number of distinct UTC hours with activity
+    pub fn active_hours(&self) -> usize {
+        self.hours.iter().filter(|h| **h > 0).count()
+    }
+
+    /// Whether enough patterns point to a signer which is always online, as opposed to
+    /// an MPC or manually operated wallet.
+    pub fn looks_automated(&self) -> bool {
+        self.signals.len() >= MIN_SIGNALS
+    }
+
+    /// Classifies a single signer account from its transaction pattern.
+    ///
+    /// Returns `AccountType::Inconclusive` when there are fewer than 2 transactions.
+    /// The confidence grows with the history size and with how clear-cut the signals are.
+    pub fn verdict(&self) -> (AccountType, Confidence) {
+        classify(self.tx_count, self.signals.len())
+    }
+
+    /// Summarizes the figures the verdict is based on.
+    pub fn evidence(&self) -> Vec<String> {
+        let mut evidence = vec![format!(
+            "{} transactions over {} active days",
+            self.tx_count, self.active_days
+        )];
+        if let (Some(p10), Some(median), Some(p90)) =
+            (self.p10_gap_secs, self.median_gap_secs, self.p90_gap_secs)
+        {
+            evidence.push(format!(
+                "gaps p10 {}, median {}, p90 {}, {:.0}% back-to-back",
+                format_secs(p10),
+                format_secs(median),
+                format_secs(p90),
+                self.back_to_back_share.unwrap_or_default() * 100.0
+            ));
+        }
+        if let Some(truncated) = &self.truncated {
+            evidence.push(format!("history {}", truncated));
+        }
+        if self.tx_count < MIN_TXS {
+            evidence.push("too little history to analyze the transaction pattern".to_string());
+        } else if self.signals.is_empty() {
+            evidence.push("no pattern of an always-online signer".to_string());
+        } else {
+            evidence.extend(self.signals.iter().cloned());
+        }
+        evidence
+    }
+
+    /// Describes the patterns pointing to a signer which is always online.
+    fn automation_signals(&self) -> Vec<String> {
+        let mut signals = Vec::new();
+        if let Some(share) = self.back_to_back_share.filter(|s| *s >= BACK_TO_BACK_SHARE) {
+            signals.push(format!(
+                "{:.0}% of consecutive transactions are back-to-back",
+                share * 100.0
+            ));
+        }
+        if let Some(median) = self.median_gap_secs.filter(|m| *m <= MEDIAN_GAP_SECS) {
+            signals.push(format!("median gap of {}", format_secs(median)));
+        }
+        if self.bursts_per_day() >= BURSTS_PER_DAY {
+            signals.push(format!(
+                "{:.1} bursts per active day",
+                self.bursts_per_day()
+            ));
+        }
+        if self.tx_count >= MIN_TXS_FOR_SPREAD && self.active_hours() >= ROUND_THE_CLOCK_HOURS {
+            signals.push(format!(
+                "active during {} different hours of the day",
+                self.active_hours()
+            ));
+        }
+        signals
+    }
+}
+
+impl fmt::Display for TxFrequency {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "Transactions: {} over {} active days (busiest day {}){}",
+            self.tx_count,
+            self.active_days,
+            self.max_txs_per_day,
+            self.truncated
+                .as_ref()
+                .map(|t| format!(", {}", t))
+                .unwrap_or_default()
+        )?;
+        let fmt_gap = |gap: Option<u64>| gap.map_or("-".to_string(), format_secs);
+        writeln!(
+            f,
+            "Gaps: min {}, p10 {}, median {}, p90 {}",
+            fmt_gap(self.min_gap_secs),
+            fmt_gap(self.p10_gap_secs),
+            fmt_gap(self.median_gap_secs),
+            fmt_gap(self.p90_gap_secs)
+        )?;
+        writeln!(
+            f,
+            "Back-to-back (<= {}): {:.0}% of gaps, {} bursts ({:.1} per active day)",
+            format_secs(self.back_to_back_secs),
+            self.back_to_back_share.unwrap_or_default() * 100.0,
+            self.bursts,
+            self.bursts_per_day()
+        )?;
+        let peak = (0..24).max_by_key(|h| self.hours[*h]).unwrap_or_default();
+        writeln!(
+            f,
+            "Active hours (UTC): {}/24, peak at {:02}:00",
+            self.active_hours(),
+            peak
+        )?;
+        let weekdays: Vec<String> = WEEKDAYS
+            .iter()
+            .zip(self.weekdays)
+            .map(|(day, count)| format!("{} {}", day, count))
+            .collect();
+        writeln!(f, "Weekdays: {}", weekdays.join(", "))?;
+        if !self.signals.is_empty() {
+            writeln!(f, "Automation signals: {}", self.signals.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Classifies a single signer account from its number of transactions and automation signals.
+fn classify(tx_count: usize, signals: usize) -> (AccountType, Confidence) {
+    if tx_count < MIN_TXS {
+        return (AccountType::Inconclusive, Confidence::Low);
+    }
+
+    // A signer which is always online transacts often and in bursts, so it's likely a hot wallet
+    let account_type = if signals >= MIN_SIGNALS {
+        AccountType::HotWallet
+    } else {
+        AccountType::MPC
+    };
+    let clear_cut = signals == 0 || signals > MIN_SIGNALS;
+    let confidence = if tx_count < MIN_TXS_CONFIDENT {
+        Confidence::Low
+    } else if clear_cut {
+        Confidence::High
+    } else {
+        Confidence::Medium
+    };
+    (account_type, confidence)
+}
+
+/// Builds the pattern figures of a history fetched newest first, one transaction at a time.
+///
+/// Alongside the transactions, it keeps running counts from which it bounds what the
+/// transactions still to come could do to the verdict, so fetching can stop once they
+/// can no longer change it.
+#[derive(Debug, Clone)]
+pub struct HistoryAnalyzer {
+    /// Maximum number of transactions that will be pushed, `None` for no limit
+    max_txs: Option<usize>,
+    /// Gap up to which consecutive transactions are back-to-back
+    back_to_back_cutoff: TimeDelta,
+    /// Close time of each transaction, newest first
+    txs: Vec<DateTime<Utc>>,
+    /// Number of back-to-back gaps
+    back_to_back: usize,
+    /// Number of gaps of at most `MEDIAN_GAP_SECS`
+    short_gaps: usize,
+    /// Runs of at least `BURST_MIN_TXS` back-to-back transactions
+    bursts: usize,
+    /// Length of the run of back-to-back transactions the oldest one belongs to
+    run: usize,
+    /// UTC days with at least one transaction
+    days: HashSet<NaiveDate>,
+    /// UTC hours with at least one transaction
+    hours: [bool; 24],
+}
+
+impl HistoryAnalyzer {
+    /// Creates an analyzer for at most `max_txs` transactions, `None` for no limit.
+    ///
+    /// # Arguments
+    /// * `max_txs` - Maximum number of transactions that will be pushed
+    /// * `back_to_back` - Gap up to which consecutive transactions are back-to-back
+    pub fn new(max_txs: Option<usize>, back_to_back: TimeDelta) -> Self {
+        Self {
+            max_txs,
+            back_to_back_cutoff: back_to_back,
+            txs: Vec::new(),
+            back_to_back: 0,
+            short_gaps: 0,
+            bursts: 0,
+            run: 0,
+            days: HashSet::new(),
+            hours: [false; 24],
+        }
+    }
+
+    /// Adds a transaction older than all the ones pushed so far.
+    pub fn push(&mut self, created_at: DateTime<Utc>) {
+        match self.txs.last() {
+            Some(newer) => {
+                let gap = secs(*newer - created_at);
+                if gap <= MEDIAN_GAP_SECS {
+                    self.short_gaps += 1;
+                }
+                if gap <= secs(self.back_to_back_cutoff) {
+                    self.back_to_back += 1;
+                    self.run += 1;
+                    if self.run == BURST_MIN_TXS {
+                        self.bursts += 1;
+                    }
+                } else {
+                    self.run = 1;
+                }
+            }
+            None => self.run = 1,
+        }
+        self.days.insert(created_at.date_naive());
+        self.hours[created_at.hour() as usize] = true;
+        self.txs.push(created_at);
+    }
+
+    /// Whether the maximum number of transactions was pushed
+    pub fn is_full(&self) -> bool {
+        self.max_txs.is_some_and(|max| self.txs.len() >= max)
+    }
+
+    /// Whether the verdict is the same whatever older transactions may still come.
+    ///
+    /// Without a maximum number of transactions, any number of them may come and
+    /// the verdict is never settled before the history ends.
+    pub fn is_settled(&self) -> bool {
+        let Some(max) = self.max_txs else {
+            return false;
+        };
+        let n = self.txs.len();
+        let remaining = max.saturating_sub(n);
+
+        // Each signal either holds whatever comes, fails whatever comes, or is still open
+        let signals = [
+            self.back_to_back_bounds(remaining),
+            self.median_gap_bounds(remaining),
+            self.bursts_bounds(remaining),
+            self.round_the_clock_bounds(remaining),
+        ];
+        let min_signals = signals.iter().filter(|(_, can_fail)| !can_fail).count();
+        let max_signals = signals.iter().filter(|(can_hold, _)| *can_hold).count();
+
+        let verdict = classify(n, min_signals);
+        classify(n + remaining, min_signals) == verdict
+            && (min_signals..=max_signals).all(|signals| {
+                classify(n, signals) == verdict && classify(n + remaining, signals) == verdict
+            })
+    }
+
+    /// Computes the pattern figures of the transactions pushed so far.
+    pub fn finish(mut self) -> TxFrequency {
+        self.txs.reverse();
+        TxFrequency::from_history(&self.txs, self.back_to_back_cutoff)
+    }
+
+    /// Number of gaps to come with up to `remaining` more transactions
+    fn gaps_to_come(&self, remaining: usize) -> usize {
+        if self.txs.is_empty() {
+            remaining.saturating_sub(1)
+        } else {
+            remaining
+        }
+    }
+
+    /// Whether the back-to-back share signal can hold and can fail
+    fn back_to_back_bounds(&self, remaining: usize) -> (bool, bool) {
+        let gaps = self.txs.len().saturating_sub(1);
+        let to_come = self.gaps_to_come(remaining);
+        let share = |back_to_back: usize, gaps: usize| back_to_back as f64 / gaps as f64;
+        let can_hold = gaps + to_come > 0
+            && share(self.back_to_back + to_come, gaps + to_come) >= BACK_TO_BACK_SHARE;
+        let can_fail = gaps == 0 || share(self.back_to_back, gaps + to_come) < BACK_TO_BACK_SHARE;
+        (can_hold, can_fail)
+    }
+
+    /// Whether the median gap signal can hold and can fail
+    fn median_gap_bounds(&self, remaining: usize) -> (bool, bool) {
+        let gaps = self.txs.len().saturating_sub(1);
+        let to_come = self.gaps_to_come(remaining);
+        // The median is short when at least as many gaps as its rank are short
+        let median_rank = rank(gaps + to_come, 50);
+        let can_hold = gaps + to_come > 0 && self.short_gaps + to_come >= median_rank;
+        let can_fail = gaps == 0 || self.short_gaps < median_rank;
+        (can_hold, can_fail)
+    }
+
+    /// Whether the bursts per active day signal can hold and can fail
+    fn bursts_bounds(&self, remaining: usize) -> (bool, bool) {
+        let days = self.days.len();
+        // New bursts take 3 transactions each, counting the oldest run if not a burst yet
+        let open_run = if self.run < BURST_MIN_TXS {
+            self.run
+        } else {
+            0
+        };
+        let max_bursts = self.bursts + (open_run + remaining) / BURST_MIN_TXS;
+        let can_hold = max_bursts as f64 / days.max(1) as f64 >= BURSTS_PER_DAY;
+        let can_fail =
+            days == 0 || (self.bursts as f64 / (days + remaining) as f64) < BURSTS_PER_DAY;
+        (can_hold, can_fail)
+    }
+
+    /// Whether the round-the-clock signal can hold and can fail
+    fn round_the_clock_bounds(&self, remaining: usize) -> (bool, bool) {
+        let n = self.txs.len();
+        let hours = self.hours.iter().filter(|h| **h).count();
+        let can_hold = n + remaining >= MIN_TXS_FOR_SPREAD
+            && (hours + remaining).min(24) >= ROUND_THE_CLOCK_HOURS;
+        let can_fail = n < MIN_TXS_FOR_SPREAD || hours < ROUND_THE_CLOCK_HOURS;
+        (can_hold, can_fail)
+    }
+}
+
+/// Whole seconds of a length of time, negative ones being taken as 0.
+fn secs(delta: TimeDelta) -> u64 {
+    delta.num_seconds().max(0) as u64
+}
+
+/// Formats seconds in the largest unit under which they fit, e.g. `45s`, `12.5m` or `3.0h`.
+fn format_secs(secs: u64) -> String {
+    match secs {
+        0..60 => format!("{}s", secs),
+        60..3600 => format!("{:.1}m", secs as f64 / 60.0),
+        3600..86400 => format!("{:.1}h", secs as f64 / 3600.0),
+        _ => format!("{:.1}d", secs as f64 / 86400.0),
+    }
+}
+
+/// Nearest rank of the `p`th percentile among `len` values, at least 1.
+fn rank(len: usize, p: usize) -> usize {
+    (p * len).div_ceil(100).max(1)
+}
+
+/// Nearest-rank percentile of sorted values.
+fn percentile(sorted: &[u64], p: usize) -> Option<u64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    sorted.get(rank(sorted.len(), p) - 1).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::duration::Duration;
+    use chrono::TimeZone;
+
+    /// Close times of transactions in the given ledgers, with 5 second ledgers
+    fn history(ledgers: &[u64]) -> Vec<DateTime<Utc>> {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        ledgers
+            .iter()
+            .map(|l| start + TimeDelta::seconds(*l as i64 * 5))
+            .collect()
+    }
+
+    fn from_history(txs: &[DateTime<Utc>]) -> TxFrequency {
+        TxFrequency::from_history(txs, TimeDelta::seconds(DEFAULT_BACK_TO_BACK_SECS))
+    }
+
+    #[test]
+    fn computes_gap_distribution() {
+        let frequency = from_history(&history(&[0, 10, 20, 1000, 1010, 5000]));
+
+        assert_eq!(frequency.tx_count, 6);
+        assert_eq!(frequency.min_gap_secs, Some(50));
+        assert_eq!(frequency.median_gap_secs, Some(50));
+        assert_eq!(frequency.p90_gap_secs, Some(19_950));
+        assert_eq!(frequency.back_to_back_share, Some(0.6));
+        assert_eq!(frequency.bursts, 1);
+        assert_eq!(frequency.active_days, 1);
+    }
+
+    #[test]
+    fn gaps_follow_close_times() {
+        // 12 ledgers apart, on a network closing a ledger every 10 seconds
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let txs: Vec<DateTime<Utc>> = (0..4)
+            .map(|i| start + TimeDelta::seconds(i * 120))
+            .collect();
+
+        let frequency = from_history(&txs);
+        assert_eq!(frequency.median_gap_secs, Some(120));
+        assert_eq!(frequency.back_to_back_share, Some(0.0));
+
+        let frequency = TxFrequency::from_history(&txs, "2m".parse::<Duration>().unwrap().0);
+        assert_eq!(frequency.back_to_back_share, Some(1.0));
+        assert_eq!(frequency.bursts, 1);
+    }
+
+    #[test]
+    fn single_burst_does_not_flip_the_verdict() {
+        // A daily transaction, with a single burst of 3 transactions
+        let mut ledgers: Vec<u64> = (0..30).map(|d| d * 17_280).collect();
+        ledgers.extend([17_285, 17_290]);
+        ledgers.sort_unstable();
+        let frequency = from_history(&history(&ledgers));
+
+        assert_eq!(frequency.min_gap_secs, Some(25));
+        assert!(!frequency.looks_automated());
+    }
+
+    #[test]
+    fn frequent_bursts_look_automated() {
+        let ledgers: Vec<u64> = (0..100).map(|i| i / 4 * 1_000 + i % 4 * 3).collect();
+        let frequency = from_history(&history(&ledgers));
+
+        assert!(frequency.looks_automated());
+    }
+
+    #[test]
+    fn no_gaps_without_history() {
+        let frequency = from_history(&history(&[42]));
+
+        assert_eq!(frequency.min_gap_secs, None);
+        assert_eq!(frequency.back_to_back_share, None);
+        assert!(!frequency.looks_automated());
+        assert!(matches!(
+            frequency.verdict(),
+            (AccountType::Inconclusive, Confidence::Low)
+        ));
+    }
+
+    #[test]
+    fn analyzer_matches_full_history() {
+        let ledgers: Vec<u64> = (0..100).map(|i| i / 4 * 1_000 + i % 4 * 3).collect();
+        let mut analyzer = HistoryAnalyzer::new(None, TimeDelta::seconds(60));
+        for created_at in history(&ledgers).into_iter().rev() {
+            analyzer.push(created_at);
+        }
+        let frequency = from_history(&history(&ledgers));
+
+        assert!(!analyzer.is_settled());
+        let analyzed = analyzer.finish();
+        assert_eq!(analyzed.median_gap_secs, frequency.median_gap_secs);
+        assert_eq!(analyzed.bursts, frequency.bursts);
+        assert_eq!(analyzed.signals, frequency.signals);
+    }
+
+    #[test]
+    fn analyzer_settles_once_the_verdict_cannot_change() {
+        // Bursts of 4 transactions about every hour
+        let ledgers: Vec<u64> = (0..600).map(|i| i / 4 * 700 + i % 4 * 3).collect();
+        let txs = history(&ledgers);
+        let mut analyzer = HistoryAnalyzer::new(Some(1000), TimeDelta::seconds(60));
+        for created_at in txs.iter().rev().take(200) {
+            analyzer.push(*created_at);
+        }
+        assert!(!analyzer.is_settled());
+        for created_at in txs.iter().rev().skip(200) {
+            analyzer.push(*created_at);
+        }
+        assert!(analyzer.is_settled());
+        assert!(matches!(
+            analyzer.finish().verdict(),
+            (AccountType::HotWallet, Confidence::High)
+        ));
+
+        // A single transaction a day is never settled on the first page
+        let ledgers: Vec<u64> = (0..200).map(|d| d * 17_280).collect();
+        let mut analyzer = HistoryAnalyzer::new(Some(1000), TimeDelta::seconds(60));
+        for created_at in history(&ledgers).into_iter().rev() {
+            analyzer.push(created_at);
+        }
+        assert!(!analyzer.is_settled());
+    }
+
+    #[test]
+    fn confidence_grows_with_history() {
+        let short = from_history(&history(&[0, 5_000, 10_000]));
+        assert!(matches!(
+            short.verdict(),
+            (AccountType::MPC, Confidence::Low)
+        ));
+
+        let ledgers: Vec<u64> = (0..30).map(|d| d * 17_280).collect();
+        let long = from_history(&history(&ledgers));
+        assert!(matches!(
+            long.verdict(),
+            (AccountType::MPC, Confidence::High)
+        ));
+    }
+}